@@ -0,0 +1,541 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use chrono::Days;
+use chrono::Local;
+use chrono::NaiveDate;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::config::Config;
+use crate::dates;
+use crate::deadline::Deadline;
+use crate::error::TaskError;
+use crate::filter::{Filter, Term};
+use crate::recurrence::Recurrence;
+use crate::reminders::{self, NotifyMethod, Reminder, Watcher};
+use crate::sort::{Sort, SortKey};
+use crate::tasks::{text_len, ChecklistItem, DESCRIPTION_CHAR_LIMIT, TITLE_CHAR_LIMIT};
+use crate::Priority;
+use crate::Task;
+use crate::TaskManager;
+use crate::TaskStatus;
+
+/// Forgetful Me - a simple task reminder software.
+///
+/// Run without arguments to open the interactive menu.
+#[derive(Debug, Parser)]
+#[command(version)]
+pub struct Cli {
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Task file to use instead of $FORGETFUL_ME_FILE or ~/.local/share/forgetful-me/tasks.json"
+    )]
+    pub file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Don't use colors (also set by the NO_COLOR environment variable)"
+    )]
+    pub no_color: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Add a new task
+    Add {
+        /// Task name
+        #[arg(long)]
+        name: String,
+        /// Task description
+        #[arg(long, default_value = "")]
+        description: String,
+        /// Longer notes, may span several lines
+        #[arg(long, default_value = "")]
+        notes: String,
+        /// Task deadline, e.g. 2026-11-01, "tomorrow 14:00", "next fri", "in 3 weeks" or eom
+        #[arg(long, value_parser = deadline_arg)]
+        deadline: Option<Deadline>,
+        /// Repeat rule: daily, weekly:mon,fri, monthly:15 or every:3 (needs --deadline)
+        #[arg(long, requires = "deadline")]
+        repeat: Option<Recurrence>,
+        /// Task priority: low, medium, high or critical
+        #[arg(long, default_value_t = Priority::Medium)]
+        priority: Priority,
+        /// Tags, comma separated or repeated
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+        /// Project the task belongs to
+        #[arg(long)]
+        project: Option<String>,
+        /// Checklist item, can be repeated
+        #[arg(long = "step", value_name = "STEP")]
+        steps: Vec<String>,
+        /// Id of a task that has to be completed first, can be repeated
+        #[arg(long = "blocked-by", value_name = "ID")]
+        blocked_by: Vec<u32>,
+        /// Reminder: 2d or 3h before the deadline, 09:00 or morning on its day,
+        /// 1d@18:00 the day before, or a date and time. Can be repeated
+        #[arg(long = "remind", value_name = "WHEN")]
+        reminders: Vec<Reminder>,
+    },
+    /// List tasks, one per line
+    List {
+        /// Filter expression, e.g. status:incomplete tag:work 'due:<7d' 'text:"two words"'
+        filter: Vec<String>,
+        /// Which tasks to list
+        #[arg(long, value_enum, default_value_t = ListStatus::All)]
+        status: ListStatus,
+        /// Order of the listed tasks
+        #[arg(long, value_enum, default_value_t = SortKey::Id)]
+        sort: SortKey,
+        /// Sort in descending order
+        #[arg(long)]
+        desc: bool,
+        /// Only list tasks with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Only list tasks in this project
+        #[arg(long)]
+        project: Option<String>,
+    },
+    /// Show everything about a task, including its notes
+    Show {
+        /// Task id
+        id: u32,
+    },
+    /// Mark tasks as complete
+    Done {
+        /// Task ids
+        #[arg(required = true)]
+        ids: Vec<u32>,
+        /// Complete tasks even if they are blocked by incomplete ones
+        #[arg(long)]
+        force: bool,
+    },
+    /// Put a task off: move its deadline, or hide it if it has none
+    Snooze {
+        /// Task id
+        id: u32,
+        /// Days to snooze for, counted from the deadline or today if that's later
+        #[arg(long, default_value_t = 1, conflicts_with = "until")]
+        days: u64,
+        /// Day to snooze until, e.g. 2026-11-01, "next mon" or "in 2 weeks"
+        #[arg(long, value_parser = date_arg)]
+        until: Option<NaiveDate>,
+    },
+    /// Mark tasks as incomplete
+    Undo {
+        /// Task ids
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Remove tasks
+    Rm {
+        /// Task ids
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Move completed tasks to the archive, printing their ids
+    Archive {
+        /// Only archive tasks completed at least this many days ago
+        #[arg(long, value_name = "DAYS", default_value_t = 0)]
+        older_than: u64,
+        /// List the archived tasks instead
+        #[arg(long, conflicts_with = "older_than")]
+        list: bool,
+    },
+    /// Move archived tasks back into the task list, printing their ids
+    Unarchive {
+        /// Task ids
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Keep running and send reminders before deadlines
+    Watch {
+        /// Check once and exit instead of running until killed
+        #[arg(long)]
+        once: bool,
+        /// Seconds between checks
+        #[arg(long, value_name = "SECONDS")]
+        interval: Option<u64>,
+        /// How to send reminders
+        #[arg(long, value_enum)]
+        notify: Option<NotifyMethod>,
+        /// Shell command to run for each reminder, implies --notify command
+        #[arg(long)]
+        command: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ListStatus {
+    All,
+    Incomplete,
+    Complete,
+}
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+fn deadline_arg(s: &str) -> Result<Deadline, String> {
+    dates::parse_deadline(s, Local::now())
+}
+
+fn date_arg(s: &str) -> Result<NaiveDate, String> {
+    dates::parse_date(s, Local::now().date_naive())
+}
+
+fn check_ids(tm: &TaskManager, ids: &[u32]) -> Result<(), TaskError> {
+    for id in ids {
+        if tm.get_task(*id).is_none() {
+            return Err(TaskError::NotFound(*id));
+        }
+    }
+    Ok(())
+}
+
+fn check_blockers(tm: &TaskManager, ids: &[u32]) -> Result<(), TaskError> {
+    for id in ids {
+        let Some(task) = tm.get_task(*id) else {
+            continue;
+        };
+        if task.status == TaskStatus::Complete {
+            continue;
+        }
+        // Blockers completed in the same command don't count.
+        let blockers: Vec<u32> = tm
+            .open_blockers(task)
+            .into_iter()
+            .filter(|b| !ids.contains(b))
+            .collect();
+        if !blockers.is_empty() {
+            return Err(TaskError::Blocked { id: *id, blockers });
+        }
+    }
+    Ok(())
+}
+
+fn task_line(task: &Task) -> String {
+    let check = if task.status == TaskStatus::Complete {
+        "[x]"
+    } else {
+        "[ ]"
+    };
+    let deadline = task
+        .date_deadline
+        .map_or_else(|| "-".to_string(), |d| d.to_string());
+    let repeat = task
+        .recurrence
+        .as_ref()
+        .map_or_else(|| "-".to_string(), ToString::to_string);
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}",
+        task.id, check, task.priority, deadline, repeat, task.name
+    )
+}
+
+fn add(tm: &mut TaskManager, n_task: Task) -> CliResult {
+    let Task {
+        name,
+        description,
+        date_deadline,
+        reminders,
+        ..
+    } = &n_task;
+
+    if name.is_empty() {
+        return Err("Task name must not be empty.".into());
+    }
+    if text_len(name) > TITLE_CHAR_LIMIT {
+        return Err(format!(
+            "Task name must be {} characters or less. Current: {}.",
+            TITLE_CHAR_LIMIT,
+            text_len(name)
+        )
+        .into());
+    }
+    if text_len(description) > DESCRIPTION_CHAR_LIMIT {
+        return Err(format!(
+            "Task description must be {} characters or less. Current: {}.",
+            DESCRIPTION_CHAR_LIMIT,
+            text_len(description)
+        )
+        .into());
+    }
+
+    if date_deadline.is_none() {
+        if let Some(r) = reminders.iter().find(|r| r.needs_deadline()) {
+            return Err(format!("The reminder '{r}' needs a --deadline.").into());
+        }
+    }
+
+    let id = tm.save_task(n_task)?;
+    println!("{id}");
+    Ok(())
+}
+
+/// Filter for `list`: the expression plus the `--status`, `--tag` and
+/// `--project` options.
+fn list_filter(
+    expression: &[String],
+    status: ListStatus,
+    tag: Option<String>,
+    project: Option<String>,
+) -> Result<Filter, Box<dyn Error>> {
+    let mut filter: Filter = expression.join(" ").parse()?;
+    match status {
+        ListStatus::All => {}
+        ListStatus::Incomplete => {
+            filter = filter.and(Term::Status(TaskStatus::Incomplete));
+            // Hidden by a snooze like in the menu's incomplete view.
+            if !Config::load()?.show_snoozed {
+                filter = filter.and(Term::Not(Box::new(Term::Snoozed)));
+            }
+        }
+        ListStatus::Complete => filter = filter.and(Term::Status(TaskStatus::Complete)),
+    }
+    if let Some(tag) = tag {
+        filter = filter.and(Term::Tag(tag));
+    }
+    if let Some(project) = project {
+        filter = filter.and(Term::Project(project));
+    }
+    Ok(filter)
+}
+
+fn list(tm: &TaskManager, filter: &Filter, sort: Sort) {
+    let mut tlist = tm.get_filtered(filter);
+    sort.apply(&mut tlist);
+
+    for task in &tlist {
+        println!("{}", task_line(task));
+    }
+}
+
+fn show(tm: &TaskManager, id: u32) -> CliResult {
+    let task = tm.get_task(id).ok_or(TaskError::NotFound(id))?;
+
+    println!("ID: {}", task.id);
+    println!("Name: {}", task.name);
+    println!("Description: {}", task.description);
+    println!("Status: {}", task.status);
+    println!("Priority: {}", task.priority);
+    println!("Date Posted: {}", task.date_posted);
+    if let Some(completed) = task.date_completed {
+        println!("Date Completed: {completed}");
+    }
+    match task.date_deadline {
+        Some(d) => println!("Deadline: {d}"),
+        None => println!("Deadline: None"),
+    }
+    if let Some(r) = &task.recurrence {
+        println!("Repeats: {r}");
+    }
+    if let Some(p) = &task.project {
+        println!("Project: {p}");
+    }
+    if !task.tags.is_empty() {
+        println!("Tags: {}", task.tags.join(", "));
+    }
+    if !task.blocked_by.is_empty() {
+        let ids: Vec<String> = task.blocked_by.iter().map(ToString::to_string).collect();
+        println!("Blocked by: {}", ids.join(", "));
+    }
+    if let Some((done, total)) = task.progress() {
+        println!("Checklist: {done}/{total}");
+        for item in &task.checklist {
+            println!("  {item}");
+        }
+    }
+    if let Some(summary) = task.snooze_summary() {
+        println!("Snoozed: {summary}");
+    }
+    if let Some(until) = task.snoozed_until {
+        println!("Hidden until: {until}");
+    }
+    if !task.reminders.is_empty() {
+        let reminders: Vec<String> = task.reminders.iter().map(ToString::to_string).collect();
+        println!("Reminders: {}", reminders.join(", "));
+    }
+    if !task.notes.is_empty() {
+        println!("Notes:");
+        for line in task.notes.lines() {
+            println!("  {line}");
+        }
+    }
+    Ok(())
+}
+
+fn archive(tm: &mut TaskManager, older_than: u64, list: bool) -> CliResult {
+    if list {
+        for task in &tm.get_archive()? {
+            println!("{}", task_line(task));
+        }
+        return Ok(());
+    }
+
+    for id in tm.archive_completed(older_than)? {
+        println!("{id}");
+    }
+    Ok(())
+}
+
+fn unarchive(tm: &mut TaskManager, ids: &[u32]) -> CliResult {
+    for id in tm.restore_archived(ids)? {
+        println!("{id}");
+    }
+    Ok(())
+}
+
+fn snooze(tm: &mut TaskManager, id: u32, days: u64, until: Option<NaiveDate>) -> CliResult {
+    let task = tm.get_task(id).ok_or(TaskError::NotFound(id))?;
+    let until = match until {
+        Some(until) => until,
+        None => task
+            .snooze_base(Local::now().date_naive())
+            .checked_add_days(Days::new(days))
+            .ok_or_else(|| format!("Can't snooze task {id} for {days} days."))?,
+    };
+    tm.snooze_task(id, until)?;
+    println!("{until}");
+    Ok(())
+}
+
+fn watch(
+    tm: &mut TaskManager,
+    path: &Path,
+    once: bool,
+    interval: Option<u64>,
+    notify: Option<NotifyMethod>,
+    command: Option<String>,
+) -> CliResult {
+    let mut config = Config::load()?.reminders;
+    if let Some(interval) = interval {
+        config.interval = interval;
+    }
+    if command.is_some() {
+        config.command = command;
+        config.notify = NotifyMethod::Command;
+    }
+    if let Some(notify) = notify {
+        config.notify = notify;
+    }
+    if config.notify == NotifyMethod::Command && config.command.is_none() {
+        return Err("Reminders are set to run a command, but none is configured. Use --command or set reminders.command in the config file.".into());
+    }
+
+    let mut watcher = Watcher::new(config, path);
+    reminders::watch(tm, &mut watcher, once)?;
+    Ok(())
+}
+
+/// Runs a single non-interactive command against the task list.
+pub fn run(command: Command, path: &Path) -> ExitCode {
+    let mut tm = TaskManager::new(path.to_path_buf());
+
+    match dispatch(&mut tm, command, path) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("forgetful-me: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn dispatch(tm: &mut TaskManager, command: Command, path: &Path) -> CliResult {
+    tm.load()?;
+
+    match command {
+        Command::Add {
+            name,
+            description,
+            notes,
+            deadline,
+            repeat,
+            priority,
+            tags,
+            project,
+            steps,
+            blocked_by,
+            reminders,
+        } => {
+            let n_task = Task {
+                id: 0,
+                name,
+                description,
+                notes: notes.trim_end().to_string(),
+                status: TaskStatus::Incomplete,
+                priority,
+                date_posted: Local::now().date_naive(),
+                date_deadline: deadline,
+                recurrence: repeat,
+                tags: Task::parse_tags(&tags.join(",")),
+                project: project.filter(|p| !p.trim().is_empty()),
+                checklist: steps
+                    .into_iter()
+                    .filter(|name| !name.trim().is_empty())
+                    .map(|name| ChecklistItem { name, done: false })
+                    .collect(),
+                blocked_by,
+                reminders,
+                original_deadline: None,
+                deferrals: 0,
+                snoozed_until: None,
+                date_completed: None,
+            };
+            add(tm, n_task)
+        }
+        Command::List {
+            filter,
+            status,
+            sort,
+            desc,
+            tag,
+            project,
+        } => {
+            let filter = list_filter(&filter, status, tag, project)?;
+            let sort = Sort {
+                key: sort,
+                descending: desc,
+            };
+            list(tm, &filter, sort);
+            Ok(())
+        }
+        Command::Show { id } => show(tm, id),
+        Command::Done { ids, force } => {
+            check_ids(tm, &ids)?;
+            if !force {
+                check_blockers(tm, &ids)
+                    .map_err(|e| format!("{e} Use --force to complete it anyway."))?;
+            }
+            tm.set_task_status(&ids, &TaskStatus::Complete)?;
+            Ok(())
+        }
+        Command::Snooze { id, days, until } => snooze(tm, id, days, until),
+        Command::Undo { ids } => {
+            check_ids(tm, &ids)?;
+            tm.set_task_status(&ids, &TaskStatus::Incomplete)?;
+            Ok(())
+        }
+        Command::Rm { ids } => {
+            check_ids(tm, &ids)?;
+            tm.delete_tasks(&ids)?;
+            Ok(())
+        }
+        Command::Archive { older_than, list } => archive(tm, older_than, list),
+        Command::Unarchive { ids } => unarchive(tm, &ids),
+        Command::Watch {
+            once,
+            interval,
+            notify,
+            command,
+        } => watch(tm, path, once, interval, notify, command),
+    }
+}
@@ -0,0 +1,76 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use strum_macros::Display;
+
+use crate::error::TaskError;
+use crate::paths;
+use crate::reminders::ReminderConfig;
+use crate::sort::Sort;
+use crate::urgency::UrgencyScale;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Display, PartialEq, Eq)]
+pub enum ListView {
+    #[default]
+    #[strum(to_string = "detailed blocks")]
+    Detailed,
+    #[strum(to_string = "a table")]
+    Table,
+}
+
+/// User preferences, kept in `~/.config/forgetful-me/config.json`.
+///
+/// Missing keys fall back to their defaults, so older config files keep working.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub sort: Sort,
+    pub view: ListView,
+    pub urgency: UrgencyScale,
+    /// Complete a task once every item of its checklist is done.
+    pub complete_when_checklist_done: bool,
+    /// Keep snoozed tasks in the incomplete view instead of hiding them.
+    pub show_snoozed: bool,
+    /// Move completed tasks to the archive this many days after they were
+    /// completed, checked when the menu opens. Never if unset.
+    pub archive_after_days: Option<u64>,
+    pub reminders: ReminderConfig,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl Config {
+    /// Reads the config file, or the defaults if there is none yet.
+    pub fn load() -> Result<Config, TaskError> {
+        let Some(path) = paths::config_file() else {
+            return Ok(Config::default());
+        };
+
+        let mut config = match fs::read_to_string(&path) {
+            Ok(contents) => {
+                serde_json::from_str::<Config>(&contents).map_err(|source| TaskError::Config {
+                    path: path.clone(),
+                    source,
+                })?
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Config::default(),
+            Err(e) => return Err(TaskError::io(&path, e)),
+        };
+        config.path = Some(path);
+        Ok(config)
+    }
+
+    pub fn save(&self) -> Result<(), TaskError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| TaskError::io(dir, e))?;
+        }
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents).map_err(|e| TaskError::io(path, e))
+    }
+}
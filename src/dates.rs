@@ -0,0 +1,315 @@
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Days;
+use chrono::Local;
+use chrono::Month;
+use chrono::Months;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use chrono::Weekday;
+
+use crate::deadline::Deadline;
+
+fn invalid(input: &str) -> String {
+    format!("Couldn't read the date '{input}', try e.g. tomorrow, next fri, in 3 weeks, eom or 2027-03-01")
+}
+
+/// First `weekday` after `today`, a week later if today is that day.
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    today + Days::new(u64::from(if ahead == 0 { 7 } else { ahead }))
+}
+
+fn last_of_month(date: NaiveDate) -> Option<NaiveDate> {
+    date.with_day(1)?
+        .checked_add_months(Months::new(1))?
+        .pred_opt()
+}
+
+/// Adds `n` days, weeks, months or years to `today`.
+fn add_units(today: NaiveDate, n: u32, unit: &str) -> Option<NaiveDate> {
+    match unit {
+        "d" | "day" | "days" => today.checked_add_days(Days::new(u64::from(n))),
+        "w" | "week" | "weeks" => today.checked_add_days(Days::new(u64::from(n) * 7)),
+        "mo" | "month" | "months" => today.checked_add_months(Months::new(n)),
+        "y" | "year" | "years" => today.checked_add_months(Months::new(n.checked_mul(12)?)),
+        _ => None,
+    }
+}
+
+/// `3 weeks`, `3weeks`, `3w` or `+3w`.
+fn parse_offset(words: &[&str], today: NaiveDate) -> Option<NaiveDate> {
+    let (number, unit) = match words {
+        [number, unit] => (number.trim_start_matches('+'), *unit),
+        [compact] => {
+            let compact = compact.trim_start_matches('+');
+            let split = compact.find(|c: char| !c.is_ascii_digit())?;
+            compact.split_at(split)
+        }
+        _ => return None,
+    };
+    add_units(today, number.parse().ok()?, unit)
+}
+
+/// `nov 1`, `1 nov` or either with a year. Without a year the next such
+/// day is meant, which may be next year.
+fn parse_month_day(words: &[&str], today: NaiveDate) -> Option<NaiveDate> {
+    let (month, day, year) = match words {
+        [a, b] | [a, b, _] => match (a.parse::<Month>(), b.parse::<u32>()) {
+            (Ok(month), Ok(day)) => (month, day, words.get(2)),
+            _ => (
+                b.parse::<Month>().ok()?,
+                a.parse::<u32>().ok()?,
+                words.get(2),
+            ),
+        },
+        _ => return None,
+    };
+    let month = month.number_from_month();
+
+    if let Some(year) = year {
+        return NaiveDate::from_ymd_opt(year.parse().ok()?, month, day);
+    }
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day);
+    match this_year {
+        Some(date) if date >= today => Some(date),
+        _ => NaiveDate::from_ymd_opt(today.year() + 1, month, day),
+    }
+}
+
+/// Parses a date the way people type it, relative to `today`:
+///
+/// - `2027-03-01`, `mar 1`, `1 march 2027`
+/// - `today`, `tomorrow`, `yesterday`
+/// - `fri`, `next friday` (the first one after today)
+/// - `next week`, `next month`, `next year` (the first day of it)
+/// - `in 3 weeks`, `3 days`, `2w`, `6mo`, `1y`
+/// - `eow`, `eom`, `eoy` (the last day of this week, month or year)
+pub fn parse_date(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let lower = input.trim().to_lowercase();
+    if let Ok(date) = NaiveDate::parse_from_str(&lower, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let words: Vec<&str> = lower.split_whitespace().collect();
+    let date = match words.as_slice() {
+        ["today" | "tod"] => Some(today),
+        ["tomorrow" | "tmr" | "tom"] => today.succ_opt(),
+        ["yesterday"] => today.pred_opt(),
+        ["eow"] => Some(next_weekday(
+            today.pred_opt().unwrap_or(today),
+            Weekday::Sun,
+        )),
+        ["eom"] => last_of_month(today),
+        ["eoy"] => NaiveDate::from_ymd_opt(today.year(), 12, 31),
+        ["next", "week"] => Some(next_weekday(today, Weekday::Mon)),
+        ["next", "month"] => today
+            .with_day(1)
+            .and_then(|d| d.checked_add_months(Months::new(1))),
+        ["next", "year"] => NaiveDate::from_ymd_opt(today.year() + 1, 1, 1),
+        ["next" | "this", day] | [day] if day.parse::<Weekday>().is_ok() => {
+            day.parse::<Weekday>().ok().map(|w| next_weekday(today, w))
+        }
+        ["in", rest @ ..] => parse_offset(rest, today),
+        _ => parse_offset(&words, today).or_else(|| parse_month_day(&words, today)),
+    };
+    date.ok_or_else(|| invalid(input.trim()))
+}
+
+/// `14:00`, `9:30`, `9am` or `2:30pm`.
+pub fn parse_time(word: &str) -> Option<NaiveTime> {
+    let word = word.trim().to_lowercase();
+    if let Ok(time) = NaiveTime::parse_from_str(&word, "%H:%M") {
+        return Some(time);
+    }
+    let (clock, pm) = match (word.strip_suffix("am"), word.strip_suffix("pm")) {
+        (Some(clock), _) => (clock, false),
+        (_, Some(clock)) => (clock, true),
+        _ => return None,
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        None => (clock.parse::<u32>().ok()?, 0),
+    };
+    if !(1..=12).contains(&hour) {
+        return None;
+    }
+    NaiveTime::from_hms_opt(hour % 12 + if pm { 12 } else { 0 }, minute, 0)
+}
+
+/// Parses a deadline: a date as understood by [`parse_date`], optionally
+/// followed by a time (`tomorrow 14:00`, `fri at 9am`), or anything
+/// `Deadline` itself reads.
+pub fn parse_deadline(input: &str, now: DateTime<Local>) -> Result<Deadline, String> {
+    if let Ok(deadline) = input.parse::<Deadline>() {
+        return Ok(deadline);
+    }
+
+    let lower = input.trim().to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+    let today = now.date_naive();
+
+    if let Some((last, rest)) = words.split_last() {
+        if let Some(time) = parse_time(last) {
+            let rest = rest.strip_suffix(&["at"]).unwrap_or(rest);
+            // A time on its own means today.
+            let date = if rest.is_empty() {
+                today
+            } else {
+                parse_date(&rest.join(" "), today).map_err(|_| invalid(input.trim()))?
+            };
+            return Deadline::at_local(date, time)
+                .ok_or_else(|| format!("{} does not exist in the local timezone", input.trim()));
+        }
+    }
+    parse_date(input, today).map(Deadline::Day)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    // A Saturday.
+    fn today() -> NaiveDate {
+        date(2026, 10, 17)
+    }
+
+    fn parse(input: &str) -> NaiveDate {
+        parse_date(input, today()).unwrap()
+    }
+
+    #[test]
+    fn relative_days() {
+        assert_eq!(parse("today"), today());
+        assert_eq!(parse("Tomorrow"), date(2026, 10, 18));
+        assert_eq!(parse("tmr"), date(2026, 10, 18));
+        assert_eq!(parse("yesterday"), date(2026, 10, 16));
+    }
+
+    #[test]
+    fn weekdays_are_the_next_one_after_today() {
+        assert_eq!(parse("fri"), date(2026, 10, 23));
+        assert_eq!(parse("next fri"), date(2026, 10, 23));
+        assert_eq!(parse("this monday"), date(2026, 10, 19));
+        assert_eq!(parse("sat"), date(2026, 10, 24));
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(parse("in 3 weeks"), date(2026, 11, 7));
+        assert_eq!(parse("3 days"), date(2026, 10, 20));
+        assert_eq!(parse("in 1 day"), date(2026, 10, 18));
+        assert_eq!(parse("2w"), date(2026, 10, 31));
+        assert_eq!(parse("+3d"), date(2026, 10, 20));
+        assert_eq!(parse("6mo"), date(2027, 4, 17));
+        assert_eq!(parse("1y"), date(2027, 10, 17));
+        assert_eq!(parse_date("1mo", date(2026, 1, 31)), Ok(date(2026, 2, 28)));
+    }
+
+    #[test]
+    fn period_starts_and_ends() {
+        assert_eq!(parse("next week"), date(2026, 10, 19));
+        assert_eq!(parse("next month"), date(2026, 11, 1));
+        assert_eq!(parse("next year"), date(2027, 1, 1));
+        assert_eq!(parse("eow"), date(2026, 10, 18));
+        assert_eq!(
+            parse_date("eow", date(2026, 10, 18)),
+            Ok(date(2026, 10, 18))
+        );
+        assert_eq!(parse("eom"), date(2026, 10, 31));
+        assert_eq!(parse_date("eom", date(2028, 2, 3)), Ok(date(2028, 2, 29)));
+        assert_eq!(parse("eoy"), date(2026, 12, 31));
+    }
+
+    #[test]
+    fn calendar_dates() {
+        assert_eq!(parse("2027-03-01"), date(2027, 3, 1));
+        assert_eq!(parse("nov 1"), date(2026, 11, 1));
+        assert_eq!(parse("oct 17"), today());
+        assert_eq!(parse("mar 1"), date(2027, 3, 1));
+        assert_eq!(parse("1 march 2027"), date(2027, 3, 1));
+        assert_eq!(parse("December 24 2030"), date(2030, 12, 24));
+    }
+
+    #[test]
+    fn invalid_dates_are_rejected() {
+        for input in [
+            "",
+            "someday",
+            "feb 30 2027",
+            "2027-02-30",
+            "in 3 fortnights",
+            "next",
+            "in",
+            "32 jan",
+            "-3d",
+        ] {
+            assert!(parse_date(input, today()).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn dates_out_of_range_are_rejected() {
+        for input in [
+            "in 99999999999 years",
+            "4294967295y",
+            "99999999w",
+            "4294967296d",
+            "in 4294967295 months",
+        ] {
+            assert!(parse_date(input, today()).is_err(), "{input}");
+        }
+        assert!(parse_date("tomorrow", NaiveDate::MAX).is_err());
+        assert!(parse_date("next year", NaiveDate::MAX).is_err());
+        assert!(parse_date("yesterday", NaiveDate::MIN).is_err());
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(parse_time("14:00"), Some(time(14, 0)));
+        assert_eq!(parse_time("9:30"), Some(time(9, 30)));
+        assert_eq!(parse_time("9am"), Some(time(9, 0)));
+        assert_eq!(parse_time("2:30PM"), Some(time(14, 30)));
+        assert_eq!(parse_time("12am"), Some(time(0, 0)));
+        assert_eq!(parse_time("12pm"), Some(time(12, 0)));
+        for input in ["13pm", "0am", "25:00", "9:60am", "noon", "9"] {
+            assert_eq!(parse_time(input), None, "{input}");
+        }
+    }
+
+    #[test]
+    fn deadlines_with_times() {
+        let now = Local.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let at = |d, t| Deadline::at_local(d, t).unwrap();
+
+        assert_eq!(
+            parse_deadline("fri at 9am", now),
+            Ok(at(date(2026, 10, 23), time(9, 0)))
+        );
+        assert_eq!(
+            parse_deadline("tomorrow 14:00", now),
+            Ok(at(date(2026, 10, 18), time(14, 0)))
+        );
+        assert_eq!(parse_deadline("18:00", now), Ok(at(today(), time(18, 0))));
+        assert_eq!(
+            parse_deadline("2026-11-01 14:00", now),
+            Ok(at(date(2026, 11, 1), time(14, 0)))
+        );
+        assert_eq!(
+            parse_deadline("in 3 weeks", now),
+            Ok(Deadline::Day(date(2026, 11, 7)))
+        );
+        assert!(parse_deadline("someday at 9am", now).is_err());
+        assert!(parse_deadline("tomorrow at", now).is_err());
+    }
+}
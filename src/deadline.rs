@@ -0,0 +1,124 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::DateTime;
+use chrono::FixedOffset;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A task deadline: either a whole day or a moment in time.
+///
+/// Timed deadlines keep the UTC offset they were set with, so they stay
+/// correct when the machine's timezone changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Deadline {
+    Day(NaiveDate),
+    At(DateTime<FixedOffset>),
+}
+
+impl Deadline {
+    /// Deadline at `time` on `date` in the local timezone.
+    pub fn at_local(date: NaiveDate, time: NaiveTime) -> Option<Deadline> {
+        let local = Local.from_local_datetime(&date.and_time(time)).earliest()?;
+        Some(Deadline::At(local.into()))
+    }
+
+    /// Calendar day of the deadline in the local timezone.
+    pub fn date(&self) -> NaiveDate {
+        match self {
+            Deadline::Day(date) => *date,
+            Deadline::At(at) => at.with_timezone(&Local).date_naive(),
+        }
+    }
+
+    /// Time of day of the deadline in the local timezone, if it has one.
+    pub fn time(&self) -> Option<NaiveTime> {
+        match self {
+            Deadline::Day(_) => None,
+            Deadline::At(at) => Some(at.with_timezone(&Local).time()),
+        }
+    }
+
+    /// Moment the deadline falls due, with whole days counting as due at `day_time`.
+    pub fn due_at(&self, day_time: NaiveTime) -> Option<DateTime<Local>> {
+        match self {
+            Deadline::Day(date) => Local
+                .from_local_datetime(&date.and_time(day_time))
+                .earliest(),
+            Deadline::At(at) => Some(at.with_timezone(&Local)),
+        }
+    }
+
+    /// Same deadline moved to another day, keeping its time of day.
+    pub fn with_date(&self, date: NaiveDate) -> Deadline {
+        match self.time() {
+            Some(time) => Deadline::at_local(date, time).unwrap_or(Deadline::Day(date)),
+            None => Deadline::Day(date),
+        }
+    }
+
+    /// Whole days left until the deadline.
+    ///
+    /// Day deadlines count calendar days, so the deadline day itself is 0.
+    /// Timed deadlines count full 24 hour periods, so anything due within a
+    /// day is 0 and anything already past is negative.
+    pub fn days_left(&self, now: DateTime<Local>) -> i64 {
+        match self {
+            Deadline::Day(date) => date.signed_duration_since(now.date_naive()).num_days(),
+            Deadline::At(at) => {
+                let remaining = at.signed_duration_since(now);
+                if remaining < chrono::Duration::zero() {
+                    -1 - (-remaining).num_days()
+                } else {
+                    remaining.num_days()
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Deadline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Deadline::Day(date) => write!(f, "{date}"),
+            Deadline::At(at) => write!(f, "{}", at.with_timezone(&Local).format("%Y-%m-%d %H:%M")),
+        }
+    }
+}
+
+/// Parses `2026-11-01`, `2026-11-01 14:00` (local time) or an RFC 3339 timestamp.
+impl FromStr for Deadline {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(at) = DateTime::parse_from_rfc3339(s) {
+            return Ok(Deadline::At(at));
+        }
+        for fmt in ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"] {
+            if let Ok(naive) = NaiveDateTime::parse_from_str(s, fmt) {
+                return Deadline::at_local(naive.date(), naive.time())
+                    .ok_or_else(|| format!("{s} does not exist in the local timezone"));
+            }
+        }
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(Deadline::Day)
+            .map_err(|_| format!("Invalid deadline '{s}', expected YYYY-MM-DD or YYYY-MM-DD HH:MM"))
+    }
+}
+
+impl<'de> Deserialize<'de> for Deadline {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
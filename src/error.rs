@@ -0,0 +1,65 @@
+use std::io;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TaskError {
+    #[error("Couldn't access {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("{} is not a valid task file: {source}", path.display())]
+    Corrupt {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("{} is not a valid config file: {source}", path.display())]
+    Config {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Couldn't serialize the task list: {0}")]
+    Serialize(#[from] serde_json::Error),
+
+    #[error("No task with id {0}.")]
+    NotFound(u32),
+
+    #[error("Couldn't read a task id from \"{0}\".")]
+    InvalidId(String),
+
+    #[error("Task {} would depend on itself: {}.", .0[0], join_ids(.0, " -> "))]
+    DependencyCycle(Vec<u32>),
+
+    #[error("Task {id} is blocked by {}.", join_ids(blockers, ", "))]
+    Blocked { id: u32, blockers: Vec<u32> },
+
+    #[error("Task {id} can only be snoozed until after {base}.")]
+    SnoozeTooEarly { id: u32, base: NaiveDate },
+
+    #[error("Task {0} is already complete.")]
+    AlreadyComplete(u32),
+}
+
+fn join_ids(ids: &[u32], sep: &str) -> String {
+    let ids: Vec<String> = ids.iter().map(ToString::to_string).collect();
+    ids.join(sep)
+}
+
+impl TaskError {
+    pub fn io(path: &std::path::Path, source: io::Error) -> Self {
+        TaskError::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+}
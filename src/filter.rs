@@ -0,0 +1,345 @@
+use std::str::FromStr;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::NaiveDate;
+
+use crate::Priority;
+use crate::Task;
+use crate::TaskStatus;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cmp {
+    Lt,
+    Le,
+    Eq,
+    Ge,
+    Gt,
+}
+
+impl Cmp {
+    /// Splits a leading `<`, `<=`, `=`, `>=` or `>` off `s`, defaulting to `=`.
+    fn split(s: &str) -> (Cmp, &str) {
+        for (prefix, cmp) in [
+            ("<=", Cmp::Le),
+            (">=", Cmp::Ge),
+            ("<", Cmp::Lt),
+            (">", Cmp::Gt),
+            ("=", Cmp::Eq),
+        ] {
+            if let Some(rest) = s.strip_prefix(prefix) {
+                return (cmp, rest);
+            }
+        }
+        (Cmp::Eq, s)
+    }
+
+    fn holds<T: Ord>(self, left: &T, right: &T) -> bool {
+        match self {
+            Cmp::Lt => left < right,
+            Cmp::Le => left <= right,
+            Cmp::Eq => left == right,
+            Cmp::Ge => left >= right,
+            Cmp::Gt => left > right,
+        }
+    }
+}
+
+/// One condition of a filter. A task has to match every term.
+#[derive(Debug, Clone)]
+pub enum Term {
+    Id(u32),
+    Status(TaskStatus),
+    Priority(Cmp, Priority),
+    Tag(String),
+    Project(String),
+    /// Compares the days left until the deadline, see `Deadline::days_left`.
+    DueIn(Cmp, i64),
+    DueOn(Cmp, NaiveDate),
+    HasDeadline(bool),
+    /// Hidden by a snooze, see `Task::is_snoozed`.
+    Snoozed,
+    /// Case-insensitive search in the name and description.
+    Text(String),
+    Not(Box<Term>),
+}
+
+impl Term {
+    fn matches(&self, task: &Task, now: DateTime<Local>) -> bool {
+        match self {
+            Term::Id(id) => task.id == *id,
+            Term::Status(status) => task.status == *status,
+            Term::Priority(cmp, priority) => cmp.holds(&task.priority, priority),
+            Term::Tag(tag) => task.has_tag(tag),
+            Term::Project(project) => task.in_project(project),
+            Term::DueIn(cmp, days) => task
+                .date_deadline
+                .is_some_and(|d| cmp.holds(&d.days_left(now), days)),
+            Term::DueOn(cmp, date) => task
+                .date_deadline
+                .is_some_and(|d| cmp.holds(&d.date(), date)),
+            Term::HasDeadline(has) => task.date_deadline.is_some() == *has,
+            Term::Snoozed => task.is_snoozed(now.date_naive()),
+            Term::Text(text) => {
+                let text = text.to_lowercase();
+                task.name.to_lowercase().contains(&text)
+                    || task.description.to_lowercase().contains(&text)
+            }
+            Term::Not(term) => !term.matches(task, now),
+        }
+    }
+
+    fn parse(token: &str, quoted: bool) -> Result<Term, String> {
+        if quoted {
+            return Ok(Term::Text(token.to_string()));
+        }
+        if let Some(rest) = token.strip_prefix('-').filter(|r| !r.is_empty()) {
+            return Ok(Term::Not(Box::new(Term::parse(rest, false)?)));
+        }
+        let Some((key, value)) = token.split_once(':') else {
+            return Ok(Term::Text(token.to_string()));
+        };
+
+        match key.to_lowercase().as_str() {
+            "id" => value
+                .parse()
+                .map(Term::Id)
+                .map_err(|_| format!("Invalid id: {value}")),
+            "status" => match value.to_lowercase().as_str() {
+                "incomplete" => Ok(Term::Status(TaskStatus::Incomplete)),
+                "complete" => Ok(Term::Status(TaskStatus::Complete)),
+                _ => Err(format!(
+                    "Invalid status '{value}', expected incomplete or complete"
+                )),
+            },
+            "priority" => {
+                let (cmp, value) = Cmp::split(value);
+                value.parse().map(|p| Term::Priority(cmp, p)).map_err(|_| {
+                    format!("Invalid priority '{value}', expected low, medium, high or critical")
+                })
+            }
+            "tag" => Ok(Term::Tag(value.trim_start_matches('#').to_string())),
+            "project" => Ok(Term::Project(value.to_string())),
+            "due" => Term::parse_due(value),
+            "snoozed" => match value.to_lowercase().as_str() {
+                "yes" | "true" => Ok(Term::Snoozed),
+                "no" | "false" => Ok(Term::Not(Box::new(Term::Snoozed))),
+                _ => Err(format!("Invalid snoozed '{value}', expected yes or no")),
+            },
+            "text" => Ok(Term::Text(value.to_string())),
+            _ => Err(format!(
+                "Unknown filter '{key}', expected id, status, priority, tag, project, due, snoozed or text"
+            )),
+        }
+    }
+
+    /// Parses the value of `due:` - `none`, `any`, `today`, `overdue`,
+    /// a number of days or weeks (`<7d`, `>=2w`) or a date (`<2026-11-01`).
+    fn parse_due(value: &str) -> Result<Term, String> {
+        match value.to_lowercase().as_str() {
+            "none" => return Ok(Term::HasDeadline(false)),
+            "any" => return Ok(Term::HasDeadline(true)),
+            "today" => return Ok(Term::DueIn(Cmp::Eq, 0)),
+            "overdue" => return Ok(Term::DueIn(Cmp::Lt, 0)),
+            _ => {}
+        }
+
+        let (cmp, rest) = Cmp::split(value);
+        if let Ok(date) = NaiveDate::parse_from_str(rest, "%Y-%m-%d") {
+            return Ok(Term::DueOn(cmp, date));
+        }
+
+        let invalid = || {
+            format!(
+                "Invalid due '{value}', expected e.g. <7d, >=2w, today, overdue, none or <2026-11-01"
+            )
+        };
+        let (number, unit) = match rest.strip_suffix(['d', 'D']) {
+            Some(n) => (n, 1),
+            None => match rest.strip_suffix(['w', 'W']) {
+                Some(n) => (n, 7),
+                None => (rest, 1),
+            },
+        };
+        let number: i64 = number.parse().map_err(|_| invalid())?;
+        number
+            .checked_mul(unit)
+            .map(|days| Term::DueIn(cmp, days))
+            .ok_or_else(invalid)
+    }
+}
+
+/// A parsed filter expression such as `status:incomplete tag:work due:<7d text:"invoice"`.
+///
+/// Terms are separated by spaces and all have to match. Quotes keep spaces
+/// inside a value, a leading `-` negates a term and a word without a key
+/// searches the name and description.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    terms: Vec<Term>,
+}
+
+impl Filter {
+    pub fn new(terms: Vec<Term>) -> Self {
+        Self { terms }
+    }
+
+    /// Adds a term that also has to match.
+    pub fn and(mut self, term: Term) -> Self {
+        self.terms.push(term);
+        self
+    }
+
+    pub fn matches(&self, task: &Task, now: DateTime<Local>) -> bool {
+        self.terms.iter().all(|t| t.matches(task, now))
+    }
+
+    /// Splits on whitespace outside of double quotes. The flag is set for
+    /// tokens that were quoted as a whole.
+    fn tokenize(s: &str) -> Result<Vec<(String, bool)>, String> {
+        let mut tokens = vec![];
+        let mut current = String::new();
+        let mut in_quotes = false;
+        let mut quoted_start = false;
+
+        for c in s.chars() {
+            match c {
+                '"' => {
+                    if !in_quotes && current.is_empty() {
+                        quoted_start = true;
+                    }
+                    in_quotes = !in_quotes;
+                }
+                c if c.is_whitespace() && !in_quotes => {
+                    if !current.is_empty() || quoted_start {
+                        tokens.push((std::mem::take(&mut current), quoted_start));
+                    }
+                    quoted_start = false;
+                }
+                c => current.push(c),
+            }
+        }
+
+        if in_quotes {
+            return Err("Missing closing quote".to_string());
+        }
+        if !current.is_empty() || quoted_start {
+            tokens.push((current, quoted_start));
+        }
+        Ok(tokens)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut terms = vec![];
+        for (token, quoted) in Filter::tokenize(s)? {
+            terms.push(Term::parse(&token, quoted)?);
+        }
+        Ok(Filter::new(terms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Days, TimeZone};
+    use serde_json::json;
+
+    use super::*;
+
+    fn now() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap()
+    }
+
+    fn task(name: &str, due_in: Option<u64>) -> Task {
+        let deadline = due_in.map(|days| {
+            (now().date_naive() + Days::new(days))
+                .format("%Y-%m-%d")
+                .to_string()
+        });
+        serde_json::from_value(json!({
+            "id": 1,
+            "name": name,
+            "description": "Quarterly report",
+            "status": "Incomplete",
+            "priority": "High",
+            "date_posted": "2026-10-01",
+            "date_deadline": deadline,
+            "tags": ["work"],
+        }))
+        .unwrap()
+    }
+
+    fn matches(filter: &str, task: &Task) -> bool {
+        filter.parse::<Filter>().unwrap().matches(task, now())
+    }
+
+    #[test]
+    fn due_in_days_and_weeks() {
+        let soon = task("Soon", Some(3));
+        let later = task("Later", Some(10));
+        let never = task("Never", None);
+
+        assert!(matches("due:<7d", &soon));
+        assert!(!matches("due:<7d", &later));
+        assert!(!matches("due:<7d", &never));
+        assert!(matches("due:>=1w", &later));
+        assert!(!matches("due:>=1W", &soon));
+        assert!(matches("due:3", &soon));
+        assert!(matches("due:today", &task("Today", Some(0))));
+        assert!(!matches("due:overdue", &soon));
+    }
+
+    #[test]
+    fn due_on_dates_and_presence() {
+        let soon = task("Soon", Some(3));
+        assert!(matches("due:<2026-11-01", &soon));
+        assert!(matches("due:=2026-10-20", &soon));
+        assert!(!matches("due:>2026-10-20", &soon));
+        assert!(matches("due:any", &soon));
+        assert!(matches("due:none", &task("Never", None)));
+    }
+
+    #[test]
+    fn invalid_due_values_are_rejected() {
+        for value in ["due:soon", "due:<", "due:7m", "due:2026-13-01"] {
+            assert!(value.parse::<Filter>().is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn due_values_out_of_range_are_rejected() {
+        assert!("due:<1537228672809129302w".parse::<Filter>().is_err());
+        assert!(format!("due:<{}d", i64::MAX).parse::<Filter>().is_ok());
+        assert!(format!("due:<{}w", i64::MAX).parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn terms_all_have_to_match() {
+        let t = task("Write report", Some(3));
+        assert!(matches("status:incomplete tag:#work priority:>=high", &t));
+        assert!(!matches("status:complete tag:work", &t));
+        assert!(!matches("priority:critical", &t));
+        assert!(matches("-tag:home id:1", &t));
+        assert!(!matches("-tag:work", &t));
+    }
+
+    #[test]
+    fn text_search_and_quotes() {
+        let t = task("Write report", None);
+        assert!(matches("REPORT", &t));
+        assert!(matches("quarterly", &t));
+        assert!(matches(r#"text:"write report""#, &t));
+        assert!(!matches(r#""report write""#, &t));
+        assert!(r#"text:"open"#.parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!("colour:red".parse::<Filter>().is_err());
+        assert!("status:done".parse::<Filter>().is_err());
+        assert!("snoozed:maybe".parse::<Filter>().is_err());
+    }
+}
@@ -1,198 +1,716 @@
 use inquire::{
-    formatter::MultiOptionFormatter, validator::Validation, Confirm, DateSelect, InquireError,
-    MultiSelect, Select, Text,
+    autocompletion::Replacement, formatter::MultiOptionFormatter, list_option::ListOption,
+    validator::Validation, Autocomplete, Confirm, CustomType, CustomUserError, DateSelect, Editor,
+    InquireError, MultiSelect, Select, Text,
 };
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 
+use chrono::DateTime;
 use chrono::Datelike;
+use chrono::Local;
 use chrono::NaiveDate;
-use chrono::Utc;
-
-use colored::*;
-
+use chrono::NaiveTime;
+use chrono::Weekday;
+
+use colored::{ColoredString, Colorize};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::config::{Config, ListView};
+use crate::dates;
+use crate::deadline::Deadline;
+use crate::error::TaskError;
+use crate::filter::{Cmp, Filter, Term};
+use crate::recurrence::Recurrence;
+use crate::reminders::Reminder;
+use crate::sort::{Sort, SortKey};
+use crate::tasks::{text_len, ChecklistItem, DESCRIPTION_CHAR_LIMIT, TITLE_CHAR_LIMIT};
+use crate::Priority;
 use crate::Task;
 use crate::TaskManager;
 use crate::TaskStatus;
 
+/// Longest description shown in the table view.
+const TABLE_DESCRIPTION_WIDTH: usize = 40;
+
+/// Renders a list of tasks for printing.
+type Formatter = fn(&Interface, &[Task]) -> String;
+
+/// Why a menu action stopped early.
+enum ActionError {
+    Prompt(InquireError),
+    Task(TaskError),
+}
+
+impl From<InquireError> for ActionError {
+    fn from(e: InquireError) -> Self {
+        ActionError::Prompt(e)
+    }
+}
+
+impl From<TaskError> for ActionError {
+    fn from(e: TaskError) -> Self {
+        ActionError::Task(e)
+    }
+}
+
+/// Suggests existing tags or projects for what is being typed. With `list`
+/// the input is comma separated and only the last entry is completed.
+#[derive(Clone)]
+struct Suggester {
+    known: Vec<String>,
+    list: bool,
+}
+
+impl Autocomplete for Suggester {
+    fn get_suggestions(&mut self, input: &str) -> Result<Vec<String>, CustomUserError> {
+        let (done, typing) = match input.rsplit_once(',') {
+            Some((done, typing)) if self.list => (format!("{done}, "), typing),
+            _ => (String::new(), input),
+        };
+        let typing = typing.trim().trim_start_matches('#').to_lowercase();
+        let already = Task::parse_tags(&done);
+
+        Ok(self
+            .known
+            .iter()
+            .filter(|k| k.to_lowercase().starts_with(&typing))
+            .filter(|k| !already.iter().any(|a| a.eq_ignore_ascii_case(k)))
+            .map(|k| format!("{done}{k}"))
+            .collect())
+    }
+
+    fn get_completion(
+        &mut self,
+        input: &str,
+        highlighted: Option<String>,
+    ) -> Result<Replacement, CustomUserError> {
+        if highlighted.is_some() {
+            return Ok(highlighted);
+        }
+        Ok(self.get_suggestions(input)?.into_iter().next())
+    }
+}
+
 pub struct Interface {
     tm: TaskManager,
+    config: Config,
 }
 
 impl Interface {
-    pub fn new() -> Self {
+    pub fn new(path: PathBuf) -> Self {
         Self {
-            tm: TaskManager::new(),
+            tm: TaskManager::new(path),
+            config: Config::default(),
         }
     }
 
-    fn clear_screen(&self) {
+    fn clear_screen() {
         // !
         print!("\x1B[2J\x1B[1;1H");
     }
 
-    fn task_create(&mut self) {
-        const TITLE_CHAR_LIMIT: u8 = 30;
-        const DESCRIPTION_CHAR_LIMIT: u8 = 100;
+    fn validate_name(t: &str) -> Validation {
+        if text_len(t) > TITLE_CHAR_LIMIT {
+            Validation::Invalid(
+                format!(
+                    "Task name must be {} characters or less. Current: {}.",
+                    TITLE_CHAR_LIMIT,
+                    text_len(t)
+                )
+                .into(),
+            )
+        } else {
+            Validation::Valid
+        }
+    }
+
+    fn validate_description(t: &str) -> Validation {
+        if text_len(t) > DESCRIPTION_CHAR_LIMIT {
+            Validation::Invalid(
+                format!(
+                    "Task description must be {} characters or less. Current: {}.",
+                    DESCRIPTION_CHAR_LIMIT,
+                    text_len(t)
+                )
+                .into(),
+            )
+        } else {
+            Validation::Valid
+        }
+    }
 
-        let task_name = Text::new("Task Name:")
-            .with_validator(|t: &str| {
-                if t.len() > TITLE_CHAR_LIMIT.into() {
-                    Ok(Validation::Invalid(
-                        format!(
-                            "Task name must be {} characters or less. Current: {}.",
-                            TITLE_CHAR_LIMIT,
-                            t.len()
-                        )
-                        .into(),
-                    ))
-                } else {
-                    Ok(Validation::Valid)
-                }
-            })
-            .prompt();
+    fn ask_priority(current: Priority) -> Result<Priority, InquireError> {
+        let cursor = Priority::ALL
+            .iter()
+            .position(|p| *p == current)
+            .unwrap_or(0);
+        Select::new("Task Priority:", Priority::ALL.to_vec())
+            .with_starting_cursor(cursor)
+            .prompt()
+    }
 
-        let task_desc = Text::new("Task Description:")
-            .with_validator(|t: &str| {
-                if t.len() > DESCRIPTION_CHAR_LIMIT.into() {
-                    Ok(Validation::Invalid(
-                        format!(
-                            "Task description must be {} characters or less. Current: {}.",
-                            DESCRIPTION_CHAR_LIMIT,
-                            t.len()
-                        )
-                        .into(),
-                    ))
-                } else {
-                    Ok(Validation::Valid)
-                }
+    fn ask_tags(current: &[String], known: Vec<String>) -> Result<Vec<String>, InquireError> {
+        let tags = Text::new("Task Tags:")
+            .with_initial_value(&current.join(", "))
+            .with_help_message("Comma separated, tab to complete, leave empty for none")
+            .with_autocomplete(Suggester { known, list: true })
+            .prompt()?;
+
+        Ok(Task::parse_tags(&tags))
+    }
+
+    fn ask_project(
+        current: Option<&str>,
+        known: Vec<String>,
+    ) -> Result<Option<String>, InquireError> {
+        let project = Text::new("Task Project:")
+            .with_initial_value(current.unwrap_or_default())
+            .with_help_message("Tab to complete, leave empty for none")
+            .with_autocomplete(Suggester { known, list: false })
+            .prompt()?;
+
+        let project = project.trim();
+        Ok((!project.is_empty()).then(|| project.to_string()))
+    }
+
+    /// Opens the notes in `$EDITOR`, or accepts them unchanged on enter.
+    fn ask_notes(current: &str) -> Result<String, InquireError> {
+        let text = if current.is_empty() {
+            String::new()
+        } else {
+            format!("{current}\n")
+        };
+        let notes = Editor::new("Task Notes:")
+            .with_predefined_text(&text)
+            .with_file_extension(".md")
+            .with_formatter(&|n| match n.trim_end().lines().count() {
+                0 => "None".to_string(),
+                1 => "1 line".to_string(),
+                lines => format!("{lines} lines"),
             })
-            .prompt();
+            .prompt()?;
+        Ok(notes.trim_end().to_string())
+    }
 
-        let current_status = TaskStatus::Incomplete;
+    /// Lets the user drop existing checklist items and add new ones.
+    fn ask_checklist(current: &[ChecklistItem]) -> Result<Vec<ChecklistItem>, InquireError> {
+        let mut checklist = vec![];
 
-        let task_date_posted = Utc::now().date_naive(); //Year-Month-Day
+        if current.is_empty() {
+            let checklist_ask = Confirm::new("Does the task have a checklist?")
+                .with_default(false)
+                .prompt()?;
+            if !checklist_ask {
+                return Ok(checklist);
+            }
+        } else {
+            let options: Vec<String> = current.iter().map(ToString::to_string).collect();
+            let all: Vec<usize> = (0..current.len()).collect();
+            let kept = MultiSelect::new("Keep which checklist items?", options)
+                .with_default(&all)
+                .raw_prompt()?;
+            checklist = kept.iter().map(|o| current[o.index].clone()).collect();
+        }
 
-        //Pre-declare
-        let deadline_choose: String;
+        loop {
+            let item = Text::new("Add checklist item:")
+                .with_help_message("Leave empty to finish")
+                .with_validator(|t: &str| {
+                    if text_len(t.trim()) > TITLE_CHAR_LIMIT {
+                        Ok(Validation::Invalid(
+                            format!(
+                                "Checklist items must be {TITLE_CHAR_LIMIT} characters or less."
+                            )
+                            .into(),
+                        ))
+                    } else {
+                        Ok(Validation::Valid)
+                    }
+                })
+                .prompt()?;
 
-        let deadline_ask = Confirm::new("Does the task have a deadline?")
-            .with_default(true)
-            .prompt();
-
-        match deadline_ask {
-            Ok(true) => {
-                deadline_choose = DateSelect::new("Choose Task Deadline:")
-                    .with_default(Utc::now().date_naive())
-                    .with_min_date(Utc::now().date_naive())
-                    .with_max_date(
-                        chrono::NaiveDate::from_ymd_opt(
-                            Utc::now().date_naive().year().saturating_add(1),
-                            12,
-                            31,
-                        )
-                        .unwrap(),
-                    )
-                    .with_week_start(chrono::Weekday::Mon)
-                    .with_help_message("Use Arrow Keys to move the cursor around")
-                    .prompt()
-                    .unwrap()
-                    .to_string();
+            let item = item.trim();
+            if item.is_empty() {
+                break;
             }
-            Ok(false) => {
-                deadline_choose = "0000-00-00".to_string();
+            checklist.push(ChecklistItem {
+                name: item.to_string(),
+                done: false,
+            });
+        }
+        Ok(checklist)
+    }
+
+    /// Lets the user drop existing reminders and add new ones. Reminders
+    /// relative to the deadline are only kept and offered with a deadline.
+    fn ask_reminders(
+        current: &[Reminder],
+        has_deadline: bool,
+    ) -> Result<Vec<Reminder>, InquireError> {
+        let current: Vec<Reminder> = current
+            .iter()
+            .copied()
+            .filter(|r| has_deadline || !r.needs_deadline())
+            .collect();
+        let mut reminders = vec![];
+
+        if current.is_empty() {
+            let reminder_ask = Confirm::new("Set reminders for the task?")
+                .with_default(false)
+                .prompt()?;
+            if !reminder_ask {
+                return Ok(reminders);
             }
-            Err(e) => {
-                panic!("{}", e);
+        } else {
+            let options: Vec<String> = current.iter().map(ToString::to_string).collect();
+            let all: Vec<usize> = (0..current.len()).collect();
+            let kept = MultiSelect::new("Keep which reminders?", options)
+                .with_default(&all)
+                .raw_prompt()?;
+            reminders = kept.iter().map(|o| current[o.index]).collect();
+        }
+
+        let help = if has_deadline {
+            "e.g. 2d or 3h before, 09:00 or morning on the day, 1d@18:00, 2026-11-01 10:00. Leave empty to finish"
+        } else {
+            "e.g. 2026-11-01 10:00. Leave empty to finish"
+        };
+        loop {
+            let reminder = Text::new("Add reminder:")
+                .with_help_message(help)
+                .with_validator(move |t: &str| {
+                    if t.trim().is_empty() {
+                        return Ok(Validation::Valid);
+                    }
+                    match t.parse::<Reminder>() {
+                        Ok(r) if r.needs_deadline() && !has_deadline => Ok(Validation::Invalid(
+                            "The task has no deadline, enter a date and time.".into(),
+                        )),
+                        Ok(_) => Ok(Validation::Valid),
+                        Err(e) => Ok(Validation::Invalid(e.into())),
+                    }
+                })
+                .prompt()?;
+
+            let Ok(reminder) = reminder.parse::<Reminder>() else {
+                break;
+            };
+            if !reminders.contains(&reminder) {
+                reminders.push(reminder);
             }
         }
+        Ok(reminders)
+    }
+
+    /// Lets the user pick the tasks this one waits on. Tasks that already
+    /// depend on `task_id` aren't offered, so no cycle can be made here.
+    fn ask_blocked_by(
+        &self,
+        task_id: Option<u32>,
+        current: &[u32],
+    ) -> Result<Vec<u32>, InquireError> {
+        let mut excluded = vec![];
+        if let Some(id) = task_id {
+            excluded = self.tm.get_dependents(id);
+            excluded.push(id);
+        }
+
+        let candidates: Vec<Task> = self
+            .tm
+            .get_tasklist()
+            .into_iter()
+            .filter(|t| !excluded.contains(&t.id))
+            .filter(|t| t.status == TaskStatus::Incomplete || current.contains(&t.id))
+            .collect();
 
-        let mut n_id = 0;
-        if self.tm.is_first_task() == false {
-            n_id = self.tm.get_last_id() + 1;
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let blocked_ask = Confirm::new("Is the task blocked by other tasks?")
+            .with_default(!current.is_empty())
+            .prompt()?;
+
+        if !blocked_ask {
+            return Ok(vec![]);
+        }
+
+        let options: Vec<String> = candidates
+            .iter()
+            .map(|t| format!("ID:{}\nName:{}", t.id, t.name))
+            .collect();
+        let selected: Vec<usize> = (0..candidates.len())
+            .filter(|i| current.contains(&candidates[*i].id))
+            .collect();
+
+        let blockers = MultiSelect::new("Which tasks have to be completed first?", options)
+            .with_default(&selected)
+            .raw_prompt()?;
+
+        Ok(blockers.iter().map(|o| candidates[o.index].id).collect())
+    }
+
+    /// Asks before completing a task that still waits on others, ignoring
+    /// blockers in `completing` that get completed along with it.
+    /// Returns true if it isn't blocked or the user wants to go ahead.
+    fn confirm_blocked_completion(
+        &self,
+        task: &Task,
+        completing: &[u32],
+    ) -> Result<bool, InquireError> {
+        let mut blockers = self.tm.open_blockers(task);
+        blockers.retain(|id| !completing.contains(id));
+        if blockers.is_empty() {
+            return Ok(true);
+        }
+
+        println!("\"{}\" is blocked by:", task.name);
+        for id in &blockers {
+            if let Some(blocker) = self.tm.get_task(*id) {
+                println!("\tID:{} {}", blocker.id, blocker.name);
+            }
+        }
+
+        Confirm::new("Complete it anyway?")
+            .with_default(false)
+            .prompt()
+    }
+
+    /// Asks whether the task has a deadline and lets the user pick it.
+    /// `current` preselects an existing deadline when editing.
+    fn ask_deadline(
+        has_deadline: bool,
+        current: Option<Deadline>,
+    ) -> Result<Option<Deadline>, InquireError> {
+        let today = Local::now().date_naive();
+        let current_date = current.map(|d| d.date());
+
+        let deadline_ask = Confirm::new("Does the task have a deadline?")
+            .with_default(has_deadline)
+            .prompt()?;
+
+        if !deadline_ask {
+            return Ok(None);
+        }
+
+        let min_date = current_date.map_or(today, |d| d.min(today));
+        let current_str = current.map(|d| d.to_string()).unwrap_or_default();
+        let typed = Text::new("Task Deadline:")
+            .with_initial_value(&current_str)
+            .with_help_message("e.g. tomorrow, next fri 14:00, in 3 weeks, eom, 2027-03-01. Leave empty to pick from a calendar")
+            .with_validator(move |t: &str| {
+                if t.trim().is_empty() {
+                    return Ok(Validation::Valid);
+                }
+                match dates::parse_deadline(t, Local::now()) {
+                    Ok(d) if d.date() < min_date => Ok(Validation::Invalid(
+                        "The deadline can't be in the past.".into(),
+                    )),
+                    Ok(_) => Ok(Validation::Valid),
+                    Err(e) => Ok(Validation::Invalid(e.into())),
+                }
+            })
+            .with_formatter(&|t| match dates::parse_deadline(t, Local::now()) {
+                Ok(d) => d.to_string(),
+                Err(_) => "Calendar".to_string(),
+            })
+            .prompt()?;
+        if let Ok(deadline) = dates::parse_deadline(&typed, Local::now()) {
+            return Ok(Some(deadline));
+        }
+
+        let date = DateSelect::new("Choose Task Deadline:")
+            .with_default(current_date.unwrap_or(today))
+            .with_min_date(min_date)
+            .with_week_start(chrono::Weekday::Mon)
+            .with_help_message("Use Arrow Keys to move the cursor around")
+            .prompt()?;
+
+        let current_time = current.and_then(|d| d.time());
+
+        let time_ask = Confirm::new("Is the deadline at a specific time?")
+            .with_default(current_time.is_some())
+            .prompt()?;
+
+        if !time_ask {
+            return Ok(Some(Deadline::Day(date)));
+        }
+
+        let time_default = current_time.map(|t| t.format("%H:%M").to_string());
+        let mut time_prompt = Text::new("Deadline time (HH:MM):").with_validator(|t: &str| {
+            if NaiveTime::parse_from_str(t, "%H:%M").is_ok() {
+                Ok(Validation::Valid)
+            } else {
+                Ok(Validation::Invalid("Time must look like 14:00.".into()))
+            }
+        });
+        if let Some(default) = &time_default {
+            time_prompt = time_prompt.with_default(default);
         }
 
+        let time = NaiveTime::parse_from_str(&time_prompt.prompt()?, "%H:%M").ok();
+
+        Ok(match time {
+            Some(time) => Deadline::at_local(date, time).or(Some(Deadline::Day(date))),
+            None => Some(Deadline::Day(date)),
+        })
+    }
+
+    fn task_create(&mut self) -> Result<(), ActionError> {
+        let task_name = Text::new("Task Name:")
+            .with_validator(|t: &str| Ok(Self::validate_name(t)))
+            .prompt()?;
+
+        let task_desc = Text::new("Task Description:")
+            .with_validator(|t: &str| Ok(Self::validate_description(t)))
+            .prompt()?;
+
+        let task_notes = Self::ask_notes("")?;
+
+        let current_status = TaskStatus::Incomplete;
+
+        let task_priority = Self::ask_priority(Priority::default())?;
+
+        let task_project = Self::ask_project(None, self.tm.get_projects())?;
+
+        let task_tags = Self::ask_tags(&[], self.tm.get_tags())?;
+
+        let task_checklist = Self::ask_checklist(&[])?;
+
+        let task_blocked_by = self.ask_blocked_by(None, &[])?;
+
+        let task_date_posted = Local::now().date_naive(); //Year-Month-Day
+
+        let deadline_choose = Self::ask_deadline(true, None)?;
+
+        let recurrence = match deadline_choose {
+            Some(d) => Self::ask_recurrence(d.date())?,
+            None => None,
+        };
+
+        let task_reminders = Self::ask_reminders(&[], deadline_choose.is_some())?;
+
         let n_task = Task {
-            id: n_id,
-            name: task_name.unwrap_or_default().to_string(),
-            description: task_desc.unwrap_or_default().to_string(),
+            id: 0,
+            name: task_name,
+            description: task_desc,
+            notes: task_notes,
             status: current_status,
-            date_posted: task_date_posted.to_string(),
+            priority: task_priority,
+            date_posted: task_date_posted,
             date_deadline: deadline_choose,
+            recurrence,
+            tags: task_tags,
+            project: task_project,
+            checklist: task_checklist,
+            blocked_by: task_blocked_by,
+            reminders: task_reminders,
+            original_deadline: None,
+            deferrals: 0,
+            snoozed_until: None,
+            date_completed: None,
         };
 
-        self.tm.save_task(n_task);
+        self.tm.save_task(n_task)?;
+        Ok(())
     }
 
-    pub fn get_colored_tasks(&mut self) -> HashMap<&str, u128> {
-        let mut colored_tasks: HashMap<&str, u128> = Default::default();
-        colored_tasks.insert("GREEN", 0);
-        colored_tasks.insert("YELLOW", 0);
-        colored_tasks.insert("RED", 0);
-        colored_tasks.insert("MAGENTA", 0);
+    fn task_edit(&mut self) -> Result<(), ActionError> {
+        let tasklist_ref = self.tm.get_tasklist();
 
-        let tlist = self.tm.get_tasklist();
+        if tasklist_ref.is_empty() {
+            println!("Task List Empty!");
+            return Ok(());
+        }
 
-        for l in 0..tlist.len() {
-            if tlist[l].status == TaskStatus::Incomplete {
-                let days: i64;
-                let mut tmp_deadline: ColoredString;
-                if tlist[l].date_deadline.eq("0000-00-00") {
-                    tmp_deadline = tlist[l].date_deadline.clone().white();
-                } else {
-                    let ymd_it = tlist[l].date_deadline.split("-");
-                    let ymd: Vec<&str> = ymd_it.collect();
-
-                    days = self.tm.get_days_diff(
-                        NaiveDate::from_ymd_opt(
-                            ymd[0].parse::<i32>().unwrap(),
-                            ymd[1].parse::<u32>().unwrap(),
-                            ymd[2].parse::<u32>().unwrap(),
-                        )
-                        .unwrap(),
-                        Utc::now().date_naive(),
-                    );
-                    tmp_deadline = tlist[l].date_deadline.clone().white();
-                    //Days to deadline
-                    if days >= 7 {
-                        colored_tasks.entry("GREEN").and_modify(|t| {
-                            *t = *t + 1;
-                        });
-                    } else if days < 7 && days > 0 {
-                        colored_tasks.entry("YELLOW").and_modify(|t| {
-                            *t = *t + 1;
-                        });
-                    } else if days == 0 {
-                        colored_tasks.entry("RED").and_modify(|t| {
-                            *t = *t + 1;
-                        });
-                    } else if days < 0 {
-                        colored_tasks.entry("MAGENTA").and_modify(|t| {
-                            *t = *t + 1;
-                        });
-                    }
-                }
+        let mut t_options = vec![];
+
+        for task in &tasklist_ref {
+            let t_edit_str = format!(
+                "ID:{}\nName:{}\nDescription:{}\nStatus:{}",
+                task.id, task.name, task.description, task.status
+            );
+            t_options.push(t_edit_str);
+        }
+
+        let edit_selection = Select::new("Select which entry to edit", t_options).prompt()?;
+
+        let id_vec = TaskManager::get_id_from_str(vec![edit_selection])?;
+        let original = self
+            .tm
+            .get_task(id_vec[0])
+            .cloned()
+            .ok_or(TaskError::NotFound(id_vec[0]))?;
+        let mut task = original.clone();
+
+        task.name = Text::new("Task Name:")
+            .with_initial_value(&task.name)
+            .with_validator(|t: &str| Ok(Self::validate_name(t)))
+            .prompt()?;
+
+        task.description = Text::new("Task Description:")
+            .with_initial_value(&task.description)
+            .with_validator(|t: &str| Ok(Self::validate_description(t)))
+            .prompt()?;
+
+        task.notes = Self::ask_notes(&task.notes)?;
+
+        task.priority = Self::ask_priority(task.priority)?;
+
+        task.project = Self::ask_project(task.project.as_deref(), self.tm.get_projects())?;
+
+        task.tags = Self::ask_tags(&task.tags, self.tm.get_tags())?;
+
+        task.checklist = Self::ask_checklist(&task.checklist)?;
+
+        task.blocked_by = self.ask_blocked_by(Some(task.id), &task.blocked_by)?;
+
+        task.date_deadline = Self::ask_deadline(task.date_deadline.is_some(), task.date_deadline)?;
+        if task.date_deadline.is_none() {
+            task.recurrence = None;
+        }
+
+        task.reminders = Self::ask_reminders(&task.reminders, task.date_deadline.is_some())?;
+
+        let status_options = vec![TaskStatus::Incomplete, TaskStatus::Complete];
+        let status_cursor = usize::from(task.status == TaskStatus::Complete);
+        let was_incomplete = task.status == TaskStatus::Incomplete;
+        task.status = Select::new("Task Status:", status_options)
+            .with_starting_cursor(status_cursor)
+            .prompt()?;
+
+        if was_incomplete
+            && task.status == TaskStatus::Complete
+            && !self.confirm_blocked_completion(&task, &[])?
+        {
+            task.status = TaskStatus::Incomplete;
+        }
+
+        self.tm.update_task(&original, task)?;
+        Ok(())
+    }
+
+    fn ask_recurrence(deadline: NaiveDate) -> Result<Option<Recurrence>, InquireError> {
+        let repeat_ask = Confirm::new("Does the task repeat?")
+            .with_default(false)
+            .prompt()?;
+
+        if !repeat_ask {
+            return Ok(None);
+        }
+
+        let rule_options = vec!["Daily", "Weekly", "Monthly", "Every N days"];
+        let rule_answer = Select::new("How often does it repeat?", rule_options).prompt()?;
+
+        let recurrence = match rule_answer {
+            "Daily" => Recurrence::Daily,
+            "Weekly" => {
+                let weekdays = vec![
+                    Weekday::Mon,
+                    Weekday::Tue,
+                    Weekday::Wed,
+                    Weekday::Thu,
+                    Weekday::Fri,
+                    Weekday::Sat,
+                    Weekday::Sun,
+                ];
+                let default_day = [deadline.weekday().num_days_from_monday() as usize];
+                let days = MultiSelect::new("Repeat on which weekdays?", weekdays)
+                    .with_default(&default_day)
+                    .with_validator(|days: &[ListOption<&Weekday>]| {
+                        if days.is_empty() {
+                            Ok(Validation::Invalid("Choose at least one weekday.".into()))
+                        } else {
+                            Ok(Validation::Valid)
+                        }
+                    })
+                    .prompt()?;
+                Recurrence::Weekly(days)
             }
+            "Monthly" => Recurrence::Monthly(
+                CustomType::<u32>::new("Repeat on which day of the month?")
+                    .with_default(deadline.day())
+                    .with_error_message("Please type a valid number")
+                    .with_validator(|day: &u32| {
+                        if (1..=31).contains(day) {
+                            Ok(Validation::Valid)
+                        } else {
+                            Ok(Validation::Invalid("Day must be between 1 and 31.".into()))
+                        }
+                    })
+                    .prompt()?,
+            ),
+            "Every N days" => Recurrence::EveryNDays(
+                CustomType::<u32>::new("Repeat every how many days?")
+                    .with_default(2)
+                    .with_error_message("Please type a valid number")
+                    .with_validator(|n: &u32| {
+                        if *n > 0 {
+                            Ok(Validation::Valid)
+                        } else {
+                            Ok(Validation::Invalid("Must be at least 1.".into()))
+                        }
+                    })
+                    .prompt()?,
+            ),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(recurrence))
+    }
+
+    /// Incomplete tasks in each band of the urgency scale.
+    pub fn get_urgency_tasks(&mut self) -> Vec<usize> {
+        let mut urgency_tasks = vec![0; self.config.urgency.bands().len()];
+
+        let now = Local::now();
+        let tlist = self.tm.get_tasklist();
+        for task in &tlist {
+            if task.status != TaskStatus::Incomplete {
+                continue;
+            }
+            if let Some(band) = task.urgency(now, &self.config.urgency) {
+                urgency_tasks[band] += 1;
+            }
+        }
+        urgency_tasks
+    }
+
+    pub fn get_priority_tasks(&mut self) -> HashMap<Priority, usize> {
+        let mut priority_tasks: HashMap<Priority, usize> = HashMap::default();
+        for p in Priority::ALL {
+            priority_tasks.insert(p, 0);
         }
 
-        colored_tasks
+        let tlist = self.tm.get_tasklist();
+        for task in &tlist {
+            if task.status == TaskStatus::Incomplete {
+                priority_tasks.entry(task.priority).and_modify(|t| {
+                    *t += 1;
+                });
+            }
+        }
+        priority_tasks
     }
 
     pub fn get_complete_tasks(&mut self) -> usize {
         let mut complete_tasks: usize = 0;
         let tlist = self.tm.get_tasklist();
-        for task in tlist.iter() {
+        for task in &tlist {
             if task.status == TaskStatus::Complete {
                 complete_tasks += 1;
             }
         }
         complete_tasks
     }
-    
+
     pub fn get_incomplete_tasks(&mut self) -> usize {
         let mut incomplete_tasks: usize = 0;
         let tlist = self.tm.get_tasklist();
-        for task in tlist.iter() {
+        for task in &tlist {
             if task.status == TaskStatus::Incomplete {
                 incomplete_tasks += 1;
             }
@@ -200,318 +718,873 @@ impl Interface {
         incomplete_tasks
     }
 
-    fn tasklist_print_incomplete(&self) {
-        let tlist = self.tm.get_tasklist();
-        let mut str_tvec = vec![];
-        for l in 0..tlist.len() {
-            if tlist[l].status == TaskStatus::Incomplete {
-                let days: i64;
-                let mut tmp_deadline: ColoredString;
-                if tlist[l].date_deadline.eq("0000-00-00") {
-                    tmp_deadline = tlist[l].date_deadline.clone().white();
-                } else {
-                    let ymd_it = tlist[l].date_deadline.split("-");
-                    let ymd: Vec<&str> = ymd_it.collect();
-                    //Compare Time to get color
-                    days = self.tm.get_days_diff(
-                        NaiveDate::from_ymd_opt(
-                            ymd[0].parse::<i32>().unwrap(),
-                            ymd[1].parse::<u32>().unwrap(),
-                            ymd[2].parse::<u32>().unwrap(),
-                        )
-                        .unwrap(),
-                        Utc::now().date_naive(),
-                    );
-                    tmp_deadline = tlist[l].date_deadline.clone().white();
-                    //Days to deadline
-                    if days >= 7 {
-                        tmp_deadline = tlist[l].date_deadline.clone().green();
-                    } else if days < 7 && days > 0 {
-                        tmp_deadline = tlist[l].date_deadline.clone().yellow();
-                    } else if days == 0 {
-                        tmp_deadline = tlist[l].date_deadline.clone().red();
-                    } else if days < 0 {
-                        tmp_deadline = tlist[l].date_deadline.clone().magenta();
-                    }
+    /// Colors `text` with the urgency band of `task`'s deadline, white without one.
+    fn color_urgency(&self, task: &Task, now: DateTime<Local>, text: &str) -> ColoredString {
+        let scale = &self.config.urgency;
+        match task.urgency(now, scale) {
+            Some(band) => text.color(scale.bands()[band].color()),
+            None => text.white(),
+        }
+    }
+
+    fn format_task(&self, task: &Task, full_notes: bool) -> String {
+        let blockers = self.tm.open_blockers(task);
+        let name = if blockers.is_empty() {
+            task.name.normal()
+        } else {
+            task.name.dimmed()
+        };
+
+        let deadline_str = task
+            .date_deadline
+            .map_or_else(|| "None".to_string(), |d| d.to_string());
+        let tmp_deadline = self.color_urgency(task, Local::now(), &deadline_str);
+
+        let mut task_string: String = format!(
+            "ID:{}\nName: {} \n Description: {} \n Status: {}\n Priority: {}\n Date Posted: {}\n Deadline: {}\n",
+            task.id,
+            name,
+            task.description,
+            task.status,
+            task.priority,
+            task.date_posted,
+            tmp_deadline
+        );
+        if let Some(completed) = task.date_completed {
+            task_string = format!("{task_string} Date Completed: {completed}\n");
+        }
+        if let Some(summary) = task.snooze_summary() {
+            task_string = format!("{task_string} Snoozed: {summary}\n");
+        }
+        if let Some(until) = task
+            .snoozed_until
+            .filter(|_| task.is_snoozed(Local::now().date_naive()))
+        {
+            task_string = format!("{task_string} Hidden until: {until}\n");
+        }
+        if let Some(r) = &task.recurrence {
+            task_string = format!("{task_string} Repeats: {r}\n");
+        }
+        if let Some(p) = &task.project {
+            task_string = format!("{task_string} Project: {p}\n");
+        }
+        if !task.tags.is_empty() {
+            task_string = format!("{task_string} Tags: {}\n", task.tags.join(", "));
+        }
+        if !blockers.is_empty() {
+            let mut names = vec![];
+            for id in &blockers {
+                if let Some(blocker) = self.tm.get_task(*id) {
+                    names.push(format!("{} (ID:{})", blocker.name, blocker.id));
                 }
-                let tmp_complete_string: String = format!(
-                    "ID:{}\nName: {} \n Description: {} \n Status: {}\n Date Posted: {}\n Deadline: {}\n",
-                    tlist[l].id.clone(),
-                    tlist[l].name.clone(),
-                    tlist[l].description.clone(),
-                    tlist[l].status.clone(),
-                    tlist[l].date_posted.clone(),
-                    tmp_deadline
-                );
-
-                str_tvec.push(tmp_complete_string);
             }
+            task_string = format!("{task_string} Blocked by: {}\n", names.join(", "));
+        }
+        if !task.reminders.is_empty() {
+            let reminders: Vec<String> = task.reminders.iter().map(ToString::to_string).collect();
+            task_string = format!("{task_string} Reminders: {}\n", reminders.join(", "));
         }
+        if let Some((done, total)) = task.progress() {
+            task_string = format!("{task_string} Checklist: {done}/{total}\n");
+            for item in &task.checklist {
+                task_string = format!("{task_string}   {item}\n");
+            }
+        }
+        // Lists only mention the notes, the detail view writes them out.
+        match task.notes.lines().count() {
+            0 => {}
+            _ if full_notes => {
+                task_string = format!("{task_string} Notes:\n");
+                for line in task.notes.lines() {
+                    task_string = format!("{task_string}   {line}\n");
+                }
+            }
+            1 => task_string = format!("{task_string} Notes: 1 line\n"),
+            lines => task_string = format!("{task_string} Notes: {lines} lines\n"),
+        }
+        task_string
+    }
 
-        println!("");
-        for i in 0..str_tvec.len() {
-            println!("{}", str_tvec[i]);
+    /// Cuts `text` down to `width` characters, marking the cut with an ellipsis.
+    fn truncate(text: &str, width: usize) -> String {
+        if text_len(text) <= width {
+            return text.to_string();
         }
-        println!("");
+        let mut cut: String = text.graphemes(true).take(width.saturating_sub(1)).collect();
+        cut.push('…');
+        cut
+    }
 
-        if str_tvec.len() == 0 {
-            println!("No Incomplete Tasks!");
+    /// One line per task with aligned columns.
+    fn format_table(&self, tlist: &[Task]) -> String {
+        let now = Local::now();
+
+        let rows: Vec<(&Task, String, String, String)> = tlist
+            .iter()
+            .map(|task| {
+                let name = match task.progress() {
+                    Some((done, total)) => format!("{} ({done}/{total})", task.name),
+                    None => task.name.clone(),
+                };
+                let deadline = task
+                    .date_deadline
+                    .map_or_else(|| "-".to_string(), |d| d.to_string());
+                let days = task
+                    .date_deadline
+                    .map_or_else(|| "-".to_string(), |d| format!("{}d", d.days_left(now)));
+                (task, name, deadline, days)
+            })
+            .collect();
+
+        let id_width = rows
+            .iter()
+            .map(|(t, _, _, _)| t.id.to_string().len())
+            .fold("ID".len(), usize::max);
+        let name_width = rows
+            .iter()
+            .map(|(_, n, _, _)| n.chars().count())
+            .fold("Name".len(), usize::max);
+        let deadline_width = rows
+            .iter()
+            .map(|(_, _, d, _)| d.len())
+            .fold("Deadline".len(), usize::max);
+        let days_width = rows
+            .iter()
+            .map(|(_, _, _, d)| d.len())
+            .fold("Days".len(), usize::max);
+
+        let mut lines = vec![format!(
+            "{:>id_width$}  {}  {:<name_width$}  {:<deadline_width$}  {:>days_width$}  {}",
+            "ID", "   ", "Name", "Deadline", "Days", "Description"
+        )];
+
+        for (task, name, deadline, days) in &rows {
+            let blocked = !self.tm.open_blockers(task).is_empty();
+            let check = if task.status == TaskStatus::Complete {
+                "[x]"
+            } else if blocked {
+                "[b]"
+            } else if task.is_snoozed(now.date_naive()) {
+                "[z]"
+            } else {
+                "[ ]"
+            };
+            let name = format!("{name:<name_width$}");
+            let name = if blocked {
+                name.dimmed()
+            } else {
+                name.normal()
+            };
+            let deadline = self.color_urgency(task, now, &format!("{deadline:<deadline_width$}"));
+            let days = self.color_urgency(task, now, &format!("{days:>days_width$}"));
+            lines.push(
+                format!(
+                    "{:>id_width$}  {}  {}  {}  {}  {}",
+                    task.id,
+                    check,
+                    name,
+                    deadline,
+                    days,
+                    Self::truncate(&task.description, TABLE_DESCRIPTION_WIDTH)
+                )
+                .trim_end()
+                .to_string(),
+            );
         }
+
+        lines.join("\n")
     }
 
-    fn tasklist_print_completed(&self) {
-        let tlist = self.tm.get_tasklist();
-        let mut str_tvec = vec![];
-        for l in 0..tlist.len() {
-            if tlist[l].status == TaskStatus::Complete {
-                let days: i64;
-                let mut tmp_deadline: ColoredString;
-                if tlist[l].date_deadline.eq("0000-00-00") {
-                    tmp_deadline = tlist[l].date_deadline.clone().white();
-                } else {
-                    let ymd_it = tlist[l].date_deadline.split("-");
-                    let ymd: Vec<&str> = ymd_it.collect();
-                    //Compare Time to get color
-                    days = self.tm.get_days_diff(
-                        NaiveDate::from_ymd_opt(
-                            ymd[0].parse::<i32>().unwrap(),
-                            ymd[1].parse::<u32>().unwrap(),
-                            ymd[2].parse::<u32>().unwrap(),
-                        )
-                        .unwrap(),
-                        Utc::now().date_naive(),
-                    );
-                    tmp_deadline = tlist[l].date_deadline.clone().white();
-                    //Days to deadline
-                    if days >= 7 {
-                        tmp_deadline = tlist[l].date_deadline.clone().green();
-                    } else if days < 7 && days > 0 {
-                        tmp_deadline = tlist[l].date_deadline.clone().yellow();
-                    } else if days == 0 {
-                        tmp_deadline = tlist[l].date_deadline.clone().red();
-                    } else if days < 0 {
-                        tmp_deadline = tlist[l].date_deadline.clone().magenta();
-                    }
-                }
-                let tmp_complete_string: String = format!(
-                    "ID:{}\nName: {} \n Description: {} \n Status: {}\n Date Posted: {}\n Deadline: {}\n",
-                    tlist[l].id.clone(),
-                    tlist[l].name.clone(),
-                    tlist[l].description.clone(),
-                    tlist[l].status.clone(),
-                    tlist[l].date_posted.clone(),
-                    tmp_deadline
-                );
-
-                str_tvec.push(tmp_complete_string);
-            }
+    /// Tasks matching `filter` in the user's sort order.
+    fn get_sorted(&self, filter: &Filter) -> Vec<Task> {
+        let mut tlist = self.tm.get_filtered(filter);
+        self.config.sort.apply(&mut tlist);
+        tlist
+    }
+
+    fn change_sort(&mut self) -> Result<(), ActionError> {
+        let sort = self.config.sort;
+
+        let key_cursor = SortKey::ALL
+            .iter()
+            .position(|k| *k == sort.key)
+            .unwrap_or(0);
+        let key = Select::new("Sort tasks by:", SortKey::ALL.to_vec())
+            .with_starting_cursor(key_cursor)
+            .prompt()?;
+
+        let order_options = vec!["Ascending", "Descending"];
+        let descending = Select::new("Sort order:", order_options)
+            .with_starting_cursor(usize::from(sort.descending))
+            .prompt()?
+            == "Descending";
+
+        self.config.sort = Sort { key, descending };
+        self.config.save()?;
+        Ok(())
+    }
+
+    fn format_detailed(&self, tlist: &[Task]) -> String {
+        let blocks: Vec<String> = tlist.iter().map(|t| self.format_task(t, false)).collect();
+        blocks.join("\n")
+    }
+
+    /// Formatter for the view the user picked.
+    fn formatter(&self) -> Formatter {
+        match self.config.view {
+            ListView::Detailed => Self::format_detailed,
+            ListView::Table => Self::format_table,
         }
+    }
 
-        println!("");
-        for i in 0..str_tvec.len() {
-            println!("{}", str_tvec[i]);
+    /// Prints the tasks matching `filter` in the user's sort order.
+    fn tasklist_render(&self, filter: &Filter, format: Formatter, empty_msg: &str) {
+        let tlist = self.get_sorted(filter);
+
+        println!();
+        if tlist.is_empty() {
+            println!("{empty_msg}");
+            return;
         }
-        println!("");
+        println!("{}", format(self, &tlist));
+        println!();
+    }
 
-        if str_tvec.len() == 0 {
-            println!("No Complete Tasks!");
+    /// The fixed lists offered in "View Task List": menu label, filter and
+    /// what to print when nothing matches.
+    fn preset_views(&self) -> Vec<(&'static str, Filter, &'static str)> {
+        let mut incomplete = Filter::default().and(Term::Status(TaskStatus::Incomplete));
+        if !self.config.show_snoozed {
+            incomplete = incomplete.and(Term::Not(Box::new(Term::Snoozed)));
         }
+        let complete = Filter::default().and(Term::Status(TaskStatus::Complete));
+        let overdue = incomplete.clone().and(Term::DueIn(Cmp::Lt, 0));
+        let this_week = incomplete
+            .clone()
+            .and(Term::DueIn(Cmp::Ge, 0))
+            .and(Term::DueIn(Cmp::Lt, 7));
+
+        vec![
+            ("Print All Tasks", Filter::default(), "Task List Empty!"),
+            ("Print Incomplete Tasks", incomplete, "No Incomplete Tasks!"),
+            ("Print Complete", complete, "No Complete Tasks!"),
+            ("Print Overdue", overdue, "No Overdue Tasks!"),
+            ("Print Due This Week", this_week, "Nothing Due This Week!"),
+        ]
     }
 
-    fn tasklist_print_all(&self) {
-        let tlist = self.tm.get_tasklist();
-        let mut str_tvec = vec![];
+    fn switch_view(&mut self) -> Result<(), ActionError> {
+        self.config.view = match self.config.view {
+            ListView::Detailed => ListView::Table,
+            ListView::Table => ListView::Detailed,
+        };
+        self.config.save()?;
+        println!("Showing tasks as {}.", self.config.view);
+        Ok(())
+    }
 
-        for l in 0..tlist.len() {
-            let days: i64;
-            let mut tmp_deadline: ColoredString;
+    fn tasklist_print_tag(&self) -> Result<(), ActionError> {
+        let tags = self.tm.get_tags();
+        if tags.is_empty() {
+            println!("No Tagged Tasks!");
+            return Ok(());
+        }
 
-            if tlist[l].date_deadline.eq("0000-00-00") {
-                tmp_deadline = tlist[l].date_deadline.clone().white();
-            } else {
-                let ymd_it = tlist[l].date_deadline.split("-");
-                let ymd: Vec<&str> = ymd_it.collect();
-
-                //Compare Time to get color
-                days = self.tm.get_days_diff(
-                    NaiveDate::from_ymd_opt(
-                        ymd[0].parse::<i32>().unwrap(),
-                        ymd[1].parse::<u32>().unwrap(),
-                        ymd[2].parse::<u32>().unwrap(),
-                    )
-                    .unwrap(),
-                    Utc::now().date_naive(),
-                );
-
-                tmp_deadline = tlist[l].date_deadline.clone().white();
-
-                //Days to deadline
-                if days >= 7 {
-                    tmp_deadline = tlist[l].date_deadline.clone().green();
-                } else if days < 7 && days > 0 {
-                    tmp_deadline = tlist[l].date_deadline.clone().yellow();
-                } else if days == 0 {
-                    tmp_deadline = tlist[l].date_deadline.clone().red();
-                } else if days < 0 {
-                    tmp_deadline = tlist[l].date_deadline.clone().magenta();
-                }
-            }
+        let tag = Select::new("Choose a tag:", tags).prompt()?;
 
-            let tmp_complete_string: String = format!(
-                "ID:{}\nName: {} \n Description: {} \n Status: {}\n Date Posted: {}\n Deadline: {}\n",
-                tlist[l].id.clone(),
-                tlist[l].name.clone(),
-                tlist[l].description.clone(),
-                tlist[l].status.clone(),
-                tlist[l].date_posted.clone(),
-                tmp_deadline
-            );
+        let filter = Filter::default().and(Term::Tag(tag));
+        self.tasklist_render(&filter, self.formatter(), "No Tagged Tasks!");
+        Ok(())
+    }
 
-            str_tvec.push(tmp_complete_string);
+    fn tasklist_print_project(&self) -> Result<(), ActionError> {
+        let projects = self.tm.get_projects();
+        if projects.is_empty() {
+            println!("No Projects!");
+            return Ok(());
         }
 
-        println!("");
-        for i in 0..str_tvec.len() {
-            println!("{}", str_tvec[i]);
+        let project = Select::new("Choose a project:", projects).prompt()?;
+
+        let filter = Filter::default().and(Term::Project(project));
+        self.tasklist_render(&filter, self.formatter(), "No Tasks In Project!");
+        Ok(())
+    }
+
+    fn tasklist_print_query(&self) -> Result<(), ActionError> {
+        let query = Text::new("Filter:")
+            .with_help_message(
+                "e.g. status:incomplete tag:work due:<7d text:\"invoice\", -tag:home to exclude",
+            )
+            .with_validator(|q: &str| match q.parse::<Filter>() {
+                Ok(_) => Ok(Validation::Valid),
+                Err(e) => Ok(Validation::Invalid(e.into())),
+            })
+            .prompt()?;
+
+        let filter = query.parse::<Filter>().unwrap_or_default();
+        self.tasklist_render(&filter, self.formatter(), "No Matching Tasks!");
+        Ok(())
+    }
+
+    fn tasklist_print_details(&self) -> Result<(), ActionError> {
+        let tasklist_ref = self.get_sorted(&Filter::default());
+
+        if tasklist_ref.is_empty() {
+            println!("Task List Empty!");
+            return Ok(());
         }
-        println!("");
+
+        let mut t_options = vec![];
+
+        for task in &tasklist_ref {
+            let t_show_str = format!(
+                "ID:{}\nName:{}\nDescription:{}\nStatus:{}",
+                task.id, task.name, task.description, task.status
+            );
+            t_options.push(t_show_str);
+        }
+
+        let show_selection = Select::new("Select which entry to show", t_options).prompt()?;
+
+        let id_vec = TaskManager::get_id_from_str(vec![show_selection])?;
+        let task = self
+            .tm
+            .get_task(id_vec[0])
+            .ok_or(TaskError::NotFound(id_vec[0]))?;
+
+        println!();
+        println!("{}", self.format_task(task, true));
+        Ok(())
     }
 
-    fn tasklist_remove(&mut self) {
-        let tasklist_ref = self.tm.get_tasklist().clone();
+    fn tasklist_remove(&mut self) -> Result<(), ActionError> {
+        let tasklist_ref = self.tm.get_tasklist();
 
         let mut t_options = vec![];
 
-        for l in 0..tasklist_ref.len() {
+        for task in &tasklist_ref {
             let t_rm_str = format!(
                 "ID:{}\nName:{}\nDescription:{}\nStatus:{}",
-                tasklist_ref[l].id.clone(),
-                tasklist_ref[l].name.clone(),
-                tasklist_ref[l].description.clone(),
-                tasklist_ref[l].status.clone()
+                task.id, task.name, task.description, task.status
             );
             t_options.push(t_rm_str);
         }
 
-        if tasklist_ref.len().eq(&0) {
+        if tasklist_ref.is_empty() {
             println!("Task List Empty!");
-        } else if tasklist_ref.len() > 0 {
+        } else {
             let formatter: MultiOptionFormatter<String> =
                 &|tasks| format!("Selected {} tasks", tasks.len());
 
             let rm_selection = MultiSelect::new("Select which entries to remove", t_options)
                 .with_help_message("↑↓ to move, space to select one, → to all, ← to none, type to filter, enter to confirm")
                 .with_formatter(formatter)
-                .prompt();
+                .prompt()?;
 
-            let rm_vec = rm_selection.unwrap_or_default();
+            let id_vec = TaskManager::get_id_from_str(rm_selection)?;
 
-            let id_vec = self.tm.get_id_from_str(rm_vec.clone());
+            self.tm.delete_tasks(&id_vec)?;
+        }
+        Ok(())
+    }
 
-            self.tm.delete_tasks(id_vec);
+    fn tasklist_mark(&mut self) -> Result<(), ActionError> {
+        let has_checklists = self
+            .tm
+            .get_tasklist()
+            .iter()
+            .any(|t| !t.checklist.is_empty());
+
+        if has_checklists {
+            let mark_options = vec!["Tasks", "Checklist Items"];
+            let which = Select::new("What do you want to mark?", mark_options).prompt()?;
+            if which == "Checklist Items" {
+                return self.checklist_mark();
+            }
         }
+        self.tasklist_mark_tasks()
     }
 
-    fn tasklist_mark(&mut self) {
-        let tasklist_ref = self.tm.get_tasklist().clone();
+    fn checklist_mark(&mut self) -> Result<(), ActionError> {
+        let tasklist_ref = self.tm.get_tasklist();
 
         let mut t_options = vec![];
 
-        for l in 0..tasklist_ref.len() {
+        for task in &tasklist_ref {
+            if let Some((done, total)) = task.progress() {
+                t_options.push(format!(
+                    "ID:{}\nName:{}\nChecklist:{done}/{total}",
+                    task.id, task.name
+                ));
+            }
+        }
+
+        let selection = Select::new("Select which task's checklist to mark", t_options).prompt()?;
+
+        let id_vec = TaskManager::get_id_from_str(vec![selection])?;
+        let task = self
+            .tm
+            .get_task(id_vec[0])
+            .cloned()
+            .ok_or(TaskError::NotFound(id_vec[0]))?;
+
+        let item_options: Vec<&str> = task.checklist.iter().map(|i| i.name.as_str()).collect();
+        let done_items: Vec<usize> = (0..task.checklist.len())
+            .filter(|i| task.checklist[*i].done)
+            .collect();
+
+        let done = MultiSelect::new("Which items are done?", item_options)
+            .with_default(&done_items)
+            .raw_prompt()?;
+        let done: Vec<usize> = done.iter().map(|o| o.index).collect();
+
+        // Only the items the user changed, so ticks saved by another session survive.
+        let marked: Vec<(usize, ChecklistItem)> = task
+            .checklist
+            .iter()
+            .enumerate()
+            .filter(|(i, item)| done.contains(i) != item.done)
+            .map(|(i, item)| {
+                let mark = ChecklistItem {
+                    done: done.contains(&i),
+                    ..item.clone()
+                };
+                (i, mark)
+            })
+            .collect();
+
+        let completed =
+            self.tm
+                .set_checklist(task.id, &marked, self.config.complete_when_checklist_done)?;
+        if completed {
+            println!("Every item is done, marked \"{}\" as complete.", task.name);
+        }
+        Ok(())
+    }
+
+    fn task_snooze(&mut self) -> Result<(), ActionError> {
+        let incomplete = Filter::default().and(Term::Status(TaskStatus::Incomplete));
+        let tasklist_ref = self.get_sorted(&incomplete);
+
+        if tasklist_ref.is_empty() {
+            println!("No Incomplete Tasks!");
+            return Ok(());
+        }
+
+        let mut t_options = vec![];
+
+        for task in &tasklist_ref {
+            let deadline = task
+                .date_deadline
+                .map_or_else(|| "None".to_string(), |d| d.to_string());
+            let t_snooze_str = format!(
+                "ID:{}\nName:{}\nDeadline:{}\nSnoozed:{}",
+                task.id, task.name, deadline, task.deferrals
+            );
+            t_options.push(t_snooze_str);
+        }
+
+        let snooze_selection = Select::new("Select which entry to snooze", t_options).prompt()?;
+
+        let id_vec = TaskManager::get_id_from_str(vec![snooze_selection])?;
+        let task = self
+            .tm
+            .get_task(id_vec[0])
+            .cloned()
+            .ok_or(TaskError::NotFound(id_vec[0]))?;
+
+        let base = task.snooze_base(Local::now().date_naive());
+        let Some(day_after) = base.succ_opt() else {
+            return Err(TaskError::SnoozeTooEarly { id: task.id, base }.into());
+        };
+        let next_monday = day_after
+            .checked_add_days(chrono::Days::new(
+                u64::from(7 - day_after.weekday().num_days_from_monday()) % 7,
+            ))
+            .unwrap_or(day_after);
+
+        let prompt = if task.date_deadline.is_some() {
+            "Move the deadline to:"
+        } else {
+            "Hide the task until:"
+        };
+        let options = vec![
+            format!("1 Day ({day_after})"),
+            format!("Next Monday ({next_monday})"),
+            "Custom Date".to_string(),
+        ];
+        let until = match Select::new(prompt, options).raw_prompt()?.index {
+            0 => day_after,
+            1 => next_monday,
+            _ => DateSelect::new("Snooze until:")
+                .with_default(day_after)
+                .with_min_date(day_after)
+                .with_week_start(Weekday::Mon)
+                .prompt()?,
+        };
+
+        self.tm.snooze_task(task.id, until)?;
+        println!("Snoozed \"{}\" until {until}.\n", task.name);
+        Ok(())
+    }
+
+    fn tasklist_mark_tasks(&mut self) -> Result<(), ActionError> {
+        let tasklist_ref = self.tm.get_tasklist();
+
+        let mut t_options = vec![];
+
+        for task in &tasklist_ref {
             let t_rm_str = format!(
                 "ID:{}\nName:{}\nDescription:{}\nStatus:{}",
-                tasklist_ref[l].id.clone(),
-                tasklist_ref[l].name.clone(),
-                tasklist_ref[l].description.clone(),
-                tasklist_ref[l].status.clone()
+                task.id, task.name, task.description, task.status
             );
             t_options.push(t_rm_str);
         }
 
-        if tasklist_ref.len().eq(&0) {
+        if tasklist_ref.is_empty() {
             println!("Task List Empty!");
-        } else if tasklist_ref.len() > 0 {
+        } else {
             let formatter: MultiOptionFormatter<String> =
                 &|tasks| format!("Selected {} tasks", tasks.len());
 
             let switch_selection = MultiSelect::new("Select which entries to switch marking", t_options)
                 .with_help_message("↑↓ to move, space to select one, → to all, ← to none, type to filter, enter to confirm")
                 .with_formatter(formatter)
-                .prompt();
+                .prompt()?;
 
-            let switch_vec = switch_selection.unwrap_or_default();
+            let mut id_vec = TaskManager::get_id_from_str(switch_selection)?;
 
-            let id_vec = self.tm.get_id_from_str(switch_vec.clone());
+            let selected = id_vec.clone();
+            for task in &tasklist_ref {
+                if selected.contains(&task.id)
+                    && task.status == TaskStatus::Incomplete
+                    && !self.confirm_blocked_completion(task, &selected)?
+                {
+                    id_vec.retain(|id| *id != task.id);
+                }
+            }
 
-            self.tm.switch_task_status(id_vec);
+            self.tm.switch_task_status(&id_vec)?;
         }
+        Ok(())
     }
 
-    fn ask_tasklist(&mut self) {
-        let which_print_hash = HashMap::from([
-            (0, "Print All Tasks"),
-            (1, "Print Incomplete Tasks"),
-            (2, "Print Complete"),
+    fn ask_tasklist(&mut self) -> Result<(), ActionError> {
+        let views = self.preset_views();
+
+        let mut which_print_vec: Vec<&str> = views.iter().map(|(label, _, _)| *label).collect();
+        which_print_vec.extend([
+            "Print by Tag",
+            "Print by Project",
+            "Filter Tasks",
+            "Show Task Details",
+            "Change Sort Order",
+            "Switch Table/Detailed View",
         ]);
 
-        let which_print_vec = vec![
-            which_print_hash[&0],
-            which_print_hash[&1],
-            which_print_hash[&2],
-        ];
-
-        let which_print: Result<&str, InquireError> =
-            Select::new("Choose an action:", which_print_vec).prompt();
-
-        match which_print {
-            Ok(which) => {
-                if which_print_hash[&0].eq(which) {
-                    self.tasklist_print_all();
-                } else if which_print_hash[&1].eq(which) {
-                    self.tasklist_print_incomplete();
-                } else if which_print_hash[&2].eq(which) {
-                    self.tasklist_print_completed();
-                }
-            }
+        let sort = self.config.sort;
+        let order = if sort.descending {
+            "descending"
+        } else {
+            "ascending"
+        };
+        let which = Select::new("Choose an action:", which_print_vec)
+            .with_help_message(&format!(
+                "Sorted by {} ({order}), shown as {}",
+                sort.key, self.config.view
+            ))
+            .prompt()?;
+
+        if let Some((_, filter, empty_msg)) = views.iter().find(|(label, _, _)| *label == which) {
+            self.tasklist_render(filter, self.formatter(), empty_msg);
+            return Ok(());
+        }
 
-            Err(_) => {
-                println!("There was an error, please try again");
-            }
+        match which {
+            "Print by Tag" => self.tasklist_print_tag()?,
+            "Print by Project" => self.tasklist_print_project()?,
+            "Filter Tasks" => self.tasklist_print_query()?,
+            "Show Task Details" => self.tasklist_print_details()?,
+            "Change Sort Order" => self.change_sort()?,
+            "Switch Table/Detailed View" => self.switch_view()?,
+            _ => {}
         }
+        Ok(())
     }
 
-    pub fn run(&mut self) {
-        self.tm.load();
+    fn print_status(&mut self) {
+        println!(
+            "\nCompleted tasks:{}, Incomplete tasks:{}",
+            self.get_complete_tasks(),
+            self.get_incomplete_tasks()
+        );
 
-        let version = env!("CARGO_PKG_VERSION");
-        print!("Forgetful Me Ver. - {}\n", version);
-        println!("A simple task reminder software.\n");
+        let urgencyt = self.get_urgency_tasks();
+        println!();
+        for (band, count) in self.config.urgency.bands().iter().zip(urgencyt) {
+            println!("\t{}: {}", band.name, count);
+        }
 
+        let priorityt = self.get_priority_tasks();
         println!(
-            "\nCompleted tasks:{}, Incomplete tasks:{}",
-            self.get_complete_tasks().clone(),
-            self.get_incomplete_tasks().clone()
+            "\n\tCritical: {}\n\tHigh: {}\n\tMedium: {}\n\tLow: {}",
+            priorityt[&Priority::Critical],
+            priorityt[&Priority::High],
+            priorityt[&Priority::Medium],
+            priorityt[&Priority::Low]
         );
 
-        {
-            let colort = self.get_colored_tasks().clone();
+        self.print_most_snoozed();
+        self.print_upcoming_reminders();
+    }
 
-            println!(
-                "\n\tGreen: {}\n\tYellow: {}\n\tRed: {}\n\tMagenta: {}",
-                colort["GREEN"], colort["YELLOW"], colort["RED"], colort["MAGENTA"]
+    /// Lists the incomplete tasks that were put off the most.
+    fn print_most_snoozed(&self) {
+        let mut snoozed: Vec<Task> = self
+            .tm
+            .get_tasklist()
+            .into_iter()
+            .filter(|t| t.status == TaskStatus::Incomplete && t.deferrals > 0)
+            .collect();
+        if snoozed.is_empty() {
+            return;
+        }
+        snoozed.sort_by_key(|t| std::cmp::Reverse(t.deferrals));
+
+        println!("\nMost snoozed:");
+        for task in snoozed.iter().take(3) {
+            println!("\t{} (ID:{}): {}", task.name, task.id, task.deferrals);
+        }
+    }
+
+    /// Lists the reminders going off within the next day.
+    fn print_upcoming_reminders(&self) {
+        let now = Local::now();
+        let mut upcoming = vec![];
+        for task in self.tm.get_tasklist() {
+            if task.status == TaskStatus::Complete {
+                continue;
+            }
+            for (reminder, at) in self.config.reminders.schedule(&task) {
+                if at > now && at <= now + chrono::Duration::days(1) {
+                    upcoming.push((at, task.name.clone(), reminder));
+                }
+            }
+        }
+        if upcoming.is_empty() {
+            return;
+        }
+        upcoming.sort_by_key(|(at, _, _)| *at);
+
+        println!("\nReminders in the next 24 hours:");
+        for (at, name, reminder) in upcoming {
+            println!("\t{}  {name} ({reminder})", at.format("%Y-%m-%d %H:%M"));
+        }
+    }
+
+    fn backup_restore(&mut self) -> Result<(), ActionError> {
+        let backups = self.tm.get_backups();
+
+        if backups.is_empty() {
+            println!("No Backups Found!");
+            return Ok(());
+        }
+
+        let mut b_options = vec![];
+
+        for backup in &backups {
+            let modified = backup.modified.map_or_else(
+                || "unknown date".to_string(),
+                |m| m.format("%Y-%m-%d %H:%M:%S").to_string(),
             );
+            let count = backup
+                .task_count
+                .map_or_else(|| "unreadable".to_string(), |c| format!("{c} tasks"));
+            let file_name = backup
+                .path
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_default();
+            b_options.push(format!("{file_name} - {modified} ({count})"));
+        }
+
+        let selection = Select::new("Select which backup to restore", b_options.clone())
+            .with_help_message("Newest first. The current list is backed up before restoring.")
+            .prompt()?;
+
+        let index = b_options.iter().position(|o| *o == selection).unwrap_or(0);
+
+        let confirm = Confirm::new("Replace the current task list with this backup?")
+            .with_default(false)
+            .prompt()?;
+
+        if confirm {
+            self.tm.restore_backup(&backups[index])?;
+            println!("Backup restored!");
+        }
+        Ok(())
+    }
+
+    fn archive_menu(&mut self) -> Result<(), ActionError> {
+        let archive_options = vec![
+            "Archive Completed Tasks",
+            "View Archive",
+            "Restore From Archive",
+        ];
+        let which = Select::new("Choose an action:", archive_options).prompt()?;
+
+        match which {
+            "Archive Completed Tasks" => self.archive_completed()?,
+            "View Archive" => self.archive_print()?,
+            "Restore From Archive" => self.archive_restore()?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn archive_completed(&mut self) -> Result<(), ActionError> {
+        let days = CustomType::<u64>::new("Archive tasks completed at least how many days ago?")
+            .with_default(self.config.archive_after_days.unwrap_or(0))
+            .with_help_message("0 archives every completed task")
+            .prompt()?;
+
+        let archived = self.tm.archive_completed(days)?;
+        if archived.is_empty() {
+            println!("No Tasks To Archive!\n");
+        } else {
+            println!("Archived {} tasks.\n", archived.len());
+        }
+        Ok(())
+    }
+
+    fn archive_print(&self) -> Result<(), ActionError> {
+        let mut archive = self.tm.get_archive()?;
+        self.config.sort.apply(&mut archive);
+
+        println!();
+        if archive.is_empty() {
+            println!("Archive Empty!");
+            return Ok(());
+        }
+        println!("{}", self.formatter()(self, &archive));
+        println!();
+        Ok(())
+    }
+
+    fn archive_restore(&mut self) -> Result<(), ActionError> {
+        let archive = self.tm.get_archive()?;
+
+        if archive.is_empty() {
+            println!("Archive Empty!");
+            return Ok(());
+        }
+
+        let mut t_options = vec![];
+
+        for task in &archive {
+            let completed = task
+                .date_completed
+                .map_or_else(|| "unknown".to_string(), |d| d.to_string());
+            t_options.push(format!(
+                "ID:{}\nName:{}\nDescription:{}\nCompleted:{}",
+                task.id, task.name, task.description, completed
+            ));
         }
 
-        print!("\n");
+        let formatter: MultiOptionFormatter<String> =
+            &|tasks| format!("Selected {} tasks", tasks.len());
+
+        let restore_selection = MultiSelect::new("Select which entries to restore", t_options)
+            .with_help_message("↑↓ to move, space to select one, → to all, ← to none, type to filter, enter to confirm")
+            .with_formatter(formatter)
+            .prompt()?;
+
+        let id_vec = TaskManager::get_id_from_str(restore_selection)?;
+
+        let restored = self.tm.restore_archived(&id_vec)?;
+        println!("Restored {} tasks.\n", restored.len());
+        Ok(())
+    }
+
+    fn load_config(&mut self) {
+        self.config = Config::load().unwrap_or_else(|e| {
+            println!("{e}\nUsing the default settings.\n");
+            Config::default()
+        });
+    }
+
+    /// Archives the tasks completed longer ago than the config allows.
+    fn auto_archive(&mut self) {
+        let Some(days) = self.config.archive_after_days else {
+            return;
+        };
+        match self.tm.archive_completed(days) {
+            Ok(archived) if archived.is_empty() => {}
+            Ok(archived) => println!("Archived {} completed tasks.\n", archived.len()),
+            Err(e) => println!("{e}\n"),
+        }
+    }
+
+    /// Offers to back up a corrupt task file and start over.
+    /// Returns false if the user would rather quit and fix it by hand.
+    fn recover_corrupt_file(&mut self, e: &TaskError) -> Result<bool, TaskError> {
+        println!("{e}\n");
+
+        let reset = Confirm::new("Back up the unreadable file and start a new task list?")
+            .with_default(false)
+            .prompt()
+            .unwrap_or_default();
+
+        if !reset {
+            return Ok(false);
+        }
+
+        let backup = self.tm.backup_and_reset()?;
+        println!("Old task file saved as {}\n", backup.display());
+        Ok(true)
+    }
+
+    pub fn run(&mut self) -> Result<(), TaskError> {
+        match self.tm.load() {
+            Ok(()) => {}
+            Err(e @ TaskError::Corrupt { .. }) => {
+                if !self.recover_corrupt_file(&e)? {
+                    return Ok(());
+                }
+            }
+            Err(e) => return Err(e),
+        }
+
+        self.load_config();
+
+        let version = env!("CARGO_PKG_VERSION");
+        println!("Forgetful Me Ver. - {version}");
+        println!("A simple task reminder software.\n");
+
+        self.auto_archive();
+
+        self.print_status();
+
+        println!();
 
         'm_loop: loop {
+            // Another session may have changed the file since the last action.
+            if let Err(e) = self.tm.refresh() {
+                println!("{e}\n");
+            }
+
             let options_hash: HashMap<u8, &str> = HashMap::from([
                 (0, "Add Task"),
-                (1, "Remove Task"),
-                (2, "Mark Task Incomplete/Complete"),
-                (3, "View Task List"),
-                (4, "Status"),
-                (5, "Quit"),
+                (1, "Edit Task"),
+                (2, "Remove Task"),
+                (3, "Mark Task Incomplete/Complete"),
+                (4, "Snooze Task"),
+                (5, "View Task List"),
+                (6, "Status"),
+                (7, "Archive"),
+                (8, "Restore Backup"),
+                (9, "Quit"),
             ]);
 
             let menu_options = vec![
@@ -521,57 +1594,80 @@ impl Interface {
                 options_hash[&3],
                 options_hash[&4],
                 options_hash[&5],
+                options_hash[&6],
+                options_hash[&7],
+                options_hash[&8],
+                options_hash[&9],
             ];
 
             let menu_answer: Result<&str, InquireError> =
                 Select::new("Choose an action:", menu_options).prompt();
 
-            match menu_answer {
+            let action_result = match menu_answer {
                 Ok(choice) => {
                     if options_hash[&0].eq(choice) {
                         //ADD
-                        self.task_create();
-                        self.clear_screen();
+                        let r = self.task_create();
+                        Self::clear_screen();
+                        r
                     } else if options_hash[&1].eq(choice) {
-                        //REMOVE
-                        self.tasklist_remove();
-                        self.clear_screen();
+                        //EDIT
+                        Self::clear_screen();
+                        let r = self.task_edit();
+                        Self::clear_screen();
+                        r
                     } else if options_hash[&2].eq(choice) {
-                        //MARK ENTRIES AS COMPLETE OR INCOMPLETE
-                        self.clear_screen();
-                        self.tasklist_mark();
+                        //REMOVE
+                        let r = self.tasklist_remove();
+                        Self::clear_screen();
+                        r
                     } else if options_hash[&3].eq(choice) {
+                        //MARK ENTRIES AS COMPLETE OR INCOMPLETE
+                        Self::clear_screen();
+                        self.tasklist_mark()
+                    } else if options_hash[&4].eq(choice) {
+                        //SNOOZE
+                        Self::clear_screen();
+                        self.task_snooze()
+                    } else if options_hash[&5].eq(choice) {
                         //VIEW
-                        self.clear_screen();
+                        Self::clear_screen();
                         //Decide which is going to be printed
-                        self.ask_tasklist();
-                    } else if options_hash[&4].eq(choice) {
+                        self.ask_tasklist()
+                    } else if options_hash[&6].eq(choice) {
                         //STATUS
-                        self.clear_screen();
-                        println!(
-                            "\nCompleted tasks:{}, Incomplete tasks:{}\n",
-                            self.get_complete_tasks().clone(),
-                            self.get_incomplete_tasks().clone()
-                        );
-                        let colort = self.get_colored_tasks().clone();
-                        println!(
-                            "\n\tGreen: {}\n\tYellow: {}\n\tRed: {}\n\tMagenta: {}",
-                            colort["GREEN"], colort["YELLOW"], colort["RED"], colort["MAGENTA"]
-                        );
+                        Self::clear_screen();
+                        self.print_status();
                         println!("\n");
-                    } else if options_hash[&5].eq(choice) {
+                        Ok(())
+                    } else if options_hash[&7].eq(choice) {
+                        //ARCHIVE
+                        Self::clear_screen();
+                        self.archive_menu()
+                    } else if options_hash[&8].eq(choice) {
+                        //RESTORE BACKUP
+                        Self::clear_screen();
+                        self.backup_restore()
+                    } else {
                         //QUIT
                         break 'm_loop;
-                    } else {
-                        panic!();
                     }
                 }
 
-                Err(e) => {
-                    panic!("{}", e);
+                Err(e) => Err(e.into()),
+            };
+
+            match action_result {
+                Ok(()) | Err(ActionError::Prompt(InquireError::OperationCanceled)) => {}
+                Err(ActionError::Prompt(InquireError::OperationInterrupted)) => break 'm_loop,
+                Err(ActionError::Prompt(e)) => {
+                    eprintln!("{e}");
+                    break 'm_loop;
                 }
+                Err(ActionError::Task(e)) => println!("{e}\n"),
             }
         }
-        self.clear_screen();
+        Self::clear_screen();
+        Ok(())
     }
 }
@@ -1,14 +1,53 @@
 #![warn(clippy::all, clippy::pedantic)]
 
+mod cli;
+mod config;
+mod dates;
+mod deadline;
+mod error;
+mod filter;
 mod interface;
+mod paths;
+mod recurrence;
+mod reminders;
+mod sort;
 mod tasks;
+mod urgency;
 
+use std::env;
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use inquire::ui::RenderConfig;
+
+use crate::cli::Cli;
+use crate::tasks::Priority;
 use crate::tasks::Task;
 use crate::tasks::TaskManager;
 use crate::tasks::TaskStatus;
 use interface::Interface;
 
-fn main() {
-    let mut interface = Interface::new();
-    interface.run();
+fn main() -> ExitCode {
+    let args = Cli::parse();
+
+    if args.no_color || env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        colored::control::set_override(false);
+        inquire::set_global_render_config(RenderConfig::empty());
+    }
+
+    let path = paths::resolve_task_file(args.file);
+
+    if let Some(command) = args.command {
+        return cli::run(command, &path);
+    }
+
+    let mut interface = Interface::new(path);
+    match interface.run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("forgetful-me: {e}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,88 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+pub const FILE_ENV_VAR: &str = "FORGETFUL_ME_FILE";
+pub const CONFIG_ENV_VAR: &str = "FORGETFUL_ME_CONFIG";
+
+const APP_DIR: &str = "forgetful-me";
+const TASK_FILE: &str = "tasks.json";
+const CONFIG_FILE: &str = "config.json";
+
+/// Picks the task file: `--file`, then `$FORGETFUL_ME_FILE`, then the
+/// per-user data directory (`~/.local/share/forgetful-me/tasks.json`).
+pub fn resolve_task_file(cli_file: Option<PathBuf>) -> PathBuf {
+    if let Some(file) = cli_file {
+        return file;
+    }
+
+    if let Some(file) = env::var_os(FILE_ENV_VAR).filter(|f| !f.is_empty()) {
+        return PathBuf::from(file);
+    }
+
+    let Some(default_file) = default_task_file() else {
+        // No home directory to speak of, keep the old behaviour.
+        return legacy_task_file().unwrap_or_else(|| PathBuf::from(TASK_FILE));
+    };
+
+    migrate_legacy_file(&default_file);
+    default_file
+}
+
+/// Picks the config file: `$FORGETFUL_ME_CONFIG`, then the per-user config
+/// directory (`~/.config/forgetful-me/config.json`).
+pub fn config_file() -> Option<PathBuf> {
+    if let Some(file) = env::var_os(CONFIG_ENV_VAR).filter(|f| !f.is_empty()) {
+        return Some(PathBuf::from(file));
+    }
+
+    let mut f = dirs::config_dir()?;
+    f.push(APP_DIR);
+    f.push(CONFIG_FILE);
+    Some(f)
+}
+
+fn default_task_file() -> Option<PathBuf> {
+    let mut f = dirs::data_dir()?;
+    f.push(APP_DIR);
+    f.push(TASK_FILE);
+    Some(f)
+}
+
+/// Where older versions kept the task file, next to the executable.
+fn legacy_task_file() -> Option<PathBuf> {
+    let mut f = env::current_exe().ok()?;
+    f.pop();
+    f.push(TASK_FILE);
+    Some(f)
+}
+
+/// Copies a task file left next to the executable by older versions to
+/// `target`, unless `target` already exists. The old file is kept as is.
+fn migrate_legacy_file(target: &PathBuf) {
+    if target.exists() {
+        return;
+    }
+    let Some(legacy) = legacy_task_file().filter(|f| f.is_file()) else {
+        return;
+    };
+
+    let copied = target
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::copy(&legacy, target));
+
+    match copied {
+        Ok(_) => eprintln!(
+            "Copied task list from {} to {}",
+            legacy.display(),
+            target.display()
+        ),
+        Err(e) => eprintln!(
+            "Couldn't copy task list from {} to {}: {}",
+            legacy.display(),
+            target.display(),
+            e
+        ),
+    }
+}
@@ -0,0 +1,226 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::Datelike;
+use chrono::Days;
+use chrono::NaiveDate;
+use chrono::Weekday;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Recurrence {
+    Daily,
+    Weekly(Vec<Weekday>),
+    Monthly(u32),
+    EveryNDays(u32),
+}
+
+impl Recurrence {
+    /// Date of the occurrence right after `from`, None past the last date
+    /// `NaiveDate` can hold.
+    pub fn next_after(&self, from: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Recurrence::Daily => from.succ_opt(),
+            Recurrence::EveryNDays(n) => from.checked_add_days(Days::new(u64::from((*n).max(1)))),
+            Recurrence::Weekly(days) => {
+                if days.is_empty() {
+                    return from.checked_add_days(Days::new(7));
+                }
+                let mut next = from.succ_opt()?;
+                while !days.contains(&next.weekday()) {
+                    next = next.succ_opt()?;
+                }
+                Some(next)
+            }
+            Recurrence::Monthly(day) => {
+                // Clamp to the last day for short months (e.g. day 31 in April).
+                let day_in = |year, month| {
+                    (1..=(*day).clamp(1, 31))
+                        .rev()
+                        .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+                };
+                if let Some(date) = day_in(from.year(), from.month()).filter(|d| *d > from) {
+                    return Some(date);
+                }
+                let (year, month) = if from.month() == 12 {
+                    (from.year() + 1, 1)
+                } else {
+                    (from.year(), from.month() + 1)
+                };
+                day_in(year, month)
+            }
+        }
+    }
+
+    /// First occurrence after `deadline` that is not before `today`.
+    pub fn next_deadline(&self, deadline: NaiveDate, today: NaiveDate) -> Option<NaiveDate> {
+        let mut next = self.next_after(deadline)?;
+        while next < today {
+            next = self.next_after(next)?;
+        }
+        Some(next)
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recurrence::Daily => write!(f, "Daily"),
+            Recurrence::Weekly(days) => {
+                let days: Vec<String> = days.iter().map(ToString::to_string).collect();
+                write!(f, "Weekly on {}", days.join(", "))
+            }
+            Recurrence::Monthly(day) => write!(f, "Monthly on day {day}"),
+            Recurrence::EveryNDays(n) => write!(f, "Every {n} days"),
+        }
+    }
+}
+
+/// Parses `daily`, `weekly:mon,fri`, `monthly:15` and `every:3`.
+impl FromStr for Recurrence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, arg) = match s.split_once(':') {
+            Some((kind, arg)) => (kind, Some(arg)),
+            None => (s, None),
+        };
+
+        match (kind.to_lowercase().as_str(), arg) {
+            ("daily", None) => Ok(Recurrence::Daily),
+            ("weekly", Some(arg)) => {
+                let mut days = vec![];
+                for d in arg.split(',') {
+                    let day = d
+                        .trim()
+                        .parse::<Weekday>()
+                        .map_err(|_| format!("Invalid weekday: {d}"))?;
+                    if !days.contains(&day) {
+                        days.push(day);
+                    }
+                }
+                Ok(Recurrence::Weekly(days))
+            }
+            ("monthly", Some(arg)) => match arg.parse::<u32>() {
+                Ok(day) if (1..=31).contains(&day) => Ok(Recurrence::Monthly(day)),
+                _ => Err(format!("Invalid day of month: {arg}")),
+            },
+            ("every", Some(arg)) => match arg.parse::<u32>() {
+                Ok(n) if n > 0 => Ok(Recurrence::EveryNDays(n)),
+                _ => Err(format!("Invalid number of days: {arg}")),
+            },
+            _ => Err(format!(
+                "Invalid recurrence '{s}', expected daily, weekly:mon,fri, monthly:15 or every:3"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn rule(s: &str) -> Recurrence {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_every_rule() {
+        assert_eq!(rule("daily"), Recurrence::Daily);
+        assert_eq!(rule("Daily"), Recurrence::Daily);
+        assert_eq!(
+            rule("weekly:mon,fri,mon"),
+            Recurrence::Weekly(vec![Weekday::Mon, Weekday::Fri])
+        );
+        assert_eq!(rule("monthly:31"), Recurrence::Monthly(31));
+        assert_eq!(rule("every:3"), Recurrence::EveryNDays(3));
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        for s in [
+            "",
+            "hourly",
+            "daily:1",
+            "weekly",
+            "weekly:someday",
+            "monthly:0",
+            "monthly:32",
+            "every:0",
+            "every:-1",
+            "every:4294967296",
+        ] {
+            assert!(s.parse::<Recurrence>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn daily_and_every_n_days() {
+        assert_eq!(
+            rule("daily").next_after(date(2026, 12, 31)),
+            Some(date(2027, 1, 1))
+        );
+        assert_eq!(
+            rule("every:3").next_after(date(2026, 2, 27)),
+            Some(date(2026, 3, 2))
+        );
+    }
+
+    #[test]
+    fn weekly_picks_the_next_listed_day() {
+        let r = rule("weekly:mon,fri");
+        // 2026-10-14 is a Wednesday.
+        assert_eq!(r.next_after(date(2026, 10, 14)), Some(date(2026, 10, 16)));
+        assert_eq!(r.next_after(date(2026, 10, 16)), Some(date(2026, 10, 19)));
+        assert_eq!(
+            Recurrence::Weekly(vec![]).next_after(date(2026, 10, 14)),
+            Some(date(2026, 10, 21))
+        );
+    }
+
+    #[test]
+    fn monthly_clamps_to_short_months() {
+        let r = rule("monthly:31");
+        assert_eq!(r.next_after(date(2026, 1, 31)), Some(date(2026, 2, 28)));
+        assert_eq!(r.next_after(date(2028, 1, 31)), Some(date(2028, 2, 29)));
+        assert_eq!(r.next_after(date(2026, 2, 28)), Some(date(2026, 3, 31)));
+        assert_eq!(r.next_after(date(2026, 3, 31)), Some(date(2026, 4, 30)));
+        assert_eq!(r.next_after(date(2026, 12, 31)), Some(date(2027, 1, 31)));
+        assert_eq!(r.next_after(date(2026, 4, 5)), Some(date(2026, 4, 30)));
+
+        // The day itself comes first if it's still ahead in `from`'s month.
+        let r = rule("monthly:15");
+        assert_eq!(r.next_after(date(2026, 12, 5)), Some(date(2026, 12, 15)));
+        assert_eq!(r.next_after(date(2026, 12, 15)), Some(date(2027, 1, 15)));
+        assert_eq!(r.next_after(date(2026, 12, 20)), Some(date(2027, 1, 15)));
+    }
+
+    #[test]
+    fn next_deadline_skips_past_occurrences() {
+        let r = rule("every:7");
+        assert_eq!(
+            r.next_deadline(date(2026, 10, 1), date(2026, 10, 17)),
+            Some(date(2026, 10, 22))
+        );
+        assert_eq!(
+            r.next_deadline(date(2026, 10, 1), date(2026, 10, 2)),
+            Some(date(2026, 10, 8))
+        );
+    }
+
+    #[test]
+    fn no_occurrence_past_the_last_date() {
+        for r in ["daily", "weekly:mon", "monthly:1", "every:1"] {
+            assert_eq!(rule(r).next_after(NaiveDate::MAX), None, "{r}");
+        }
+        assert_eq!(
+            rule("every:100000000").next_deadline(date(2026, 10, 17), date(2026, 10, 17)),
+            None
+        );
+    }
+}
@@ -0,0 +1,616 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::thread;
+
+use chrono::DateTime;
+use chrono::Days;
+use chrono::Duration;
+use chrono::Local;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+
+use clap::ValueEnum;
+
+use notify_rust::Notification;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::dates;
+use crate::deadline::Deadline;
+use crate::error::TaskError;
+use crate::Task;
+use crate::TaskManager;
+use crate::TaskStatus;
+
+/// How long after a deadline its reminders still go off, e.g. when the
+/// watcher was started late or the machine was asleep.
+const GRACE_MINUTES: i64 = 60;
+
+/// How long before a deadline a reminder goes off, e.g. `1d`, `2h` or `30m`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LeadTime {
+    minutes: i64,
+}
+
+const UNITS: [(char, i64); 4] = [('w', 7 * 24 * 60), ('d', 24 * 60), ('h', 60), ('m', 1)];
+
+impl LeadTime {
+    fn duration(self) -> Duration {
+        Duration::minutes(self.minutes)
+    }
+}
+
+impl fmt::Display for LeadTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Largest unit that divides it evenly, so `1440m` reads as `1d`.
+        let (unit, per) = UNITS
+            .into_iter()
+            .find(|(_, per)| self.minutes >= *per && self.minutes % per == 0)
+            .unwrap_or(('m', 1));
+        write!(f, "{}{unit}", self.minutes / per)
+    }
+}
+
+impl FromStr for LeadTime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || format!("Invalid lead time '{s}', expected e.g. 1w, 2d, 3h or 30m");
+
+        let unit = s.chars().last().ok_or_else(invalid)?;
+        let per = UNITS
+            .into_iter()
+            .find(|(u, _)| *u == unit.to_ascii_lowercase())
+            .map(|(_, per)| per)
+            .ok_or_else(invalid)?;
+        let number: i64 = s[..s.len() - unit.len_utf8()]
+            .parse()
+            .map_err(|_| invalid())?;
+        if number < 0 {
+            return Err(invalid());
+        }
+        let minutes = number.checked_mul(per).ok_or_else(invalid)?;
+        // Anything longer can't be subtracted from a deadline.
+        Duration::try_minutes(minutes).ok_or_else(invalid)?;
+        Ok(LeadTime { minutes })
+    }
+}
+
+impl Serialize for LeadTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for LeadTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A reminder set on a single task.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Reminder {
+    /// Some time before the deadline.
+    Before(LeadTime),
+    /// At a time of day on the deadline's day, or `days_before` days earlier.
+    DayOf { days_before: u32, time: NaiveTime },
+    /// At a fixed moment, whether or not the task has a deadline.
+    On(Deadline),
+}
+
+impl Reminder {
+    /// Whether it is set relative to the deadline.
+    pub fn needs_deadline(self) -> bool {
+        !matches!(self, Reminder::On(_))
+    }
+
+    /// When it goes off for a task due at `deadline`. Whole-day deadlines
+    /// and dates count as due at `day_time`. None if that is out of range.
+    pub fn fire_at(
+        self,
+        deadline: Option<Deadline>,
+        day_time: NaiveTime,
+    ) -> Option<DateTime<Local>> {
+        match self {
+            Reminder::Before(lead) => deadline?
+                .due_at(day_time)?
+                .checked_sub_signed(lead.duration()),
+            Reminder::DayOf { days_before, time } => {
+                let date = deadline?
+                    .date()
+                    .checked_sub_days(Days::new(u64::from(days_before)))?;
+                Local.from_local_datetime(&date.and_time(time)).earliest()
+            }
+            Reminder::On(at) => at.due_at(day_time),
+        }
+    }
+}
+
+impl fmt::Display for Reminder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reminder::Before(lead) => write!(f, "{lead} before"),
+            Reminder::DayOf { days_before, time } => {
+                let time = time.format("%H:%M");
+                match days_before {
+                    0 => write!(f, "on the day at {time}"),
+                    1 => write!(f, "the day before at {time}"),
+                    n => write!(f, "{n} days before at {time}"),
+                }
+            }
+            Reminder::On(at) => write!(f, "at {at}"),
+        }
+    }
+}
+
+/// Parses `2d` or `3h` (before the deadline), `09:00` (on the deadline's
+/// day), `1d@18:00` (the day before at 18:00), `morning` (on the day at
+/// 09:00) or a date with an optional time, see `dates::parse_deadline`.
+impl FromStr for Reminder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || {
+            format!(
+                "Invalid reminder '{s}', expected e.g. 2d, 3h, 09:00, 1d@18:00, morning or 2026-11-01 10:00"
+            )
+        };
+
+        if s.eq_ignore_ascii_case("morning") {
+            let time = NaiveTime::from_hms_opt(9, 0, 0).ok_or_else(invalid)?;
+            return Ok(Reminder::DayOf {
+                days_before: 0,
+                time,
+            });
+        }
+        if let Ok(lead) = s.parse::<LeadTime>() {
+            return Ok(Reminder::Before(lead));
+        }
+        if let Some(time) = dates::parse_time(s) {
+            return Ok(Reminder::DayOf {
+                days_before: 0,
+                time,
+            });
+        }
+        if let Some((days, time)) = s.split_once('@') {
+            let days = days.trim();
+            let days = days
+                .strip_suffix(['d', 'D'])
+                .unwrap_or(days)
+                .parse()
+                .map_err(|_| invalid())?;
+            let time = dates::parse_time(time).ok_or_else(invalid)?;
+            return Ok(Reminder::DayOf {
+                days_before: days,
+                time,
+            });
+        }
+        dates::parse_deadline(s, Local::now())
+            .map(Reminder::On)
+            .map_err(|_| invalid())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyMethod {
+    /// Desktop notification over D-Bus
+    #[default]
+    Desktop,
+    /// Terminal bell
+    Bell,
+    /// Run the configured command
+    Command,
+}
+
+/// Settings for `forgetful-me watch`, the `reminders` key of the config file.
+///
+/// The lead times apply to tasks that don't have reminders of their own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReminderConfig {
+    /// How long before each deadline to remind.
+    pub lead_times: Vec<LeadTime>,
+    /// Time of day whole-day deadlines count as due.
+    pub day_time: NaiveTime,
+    pub notify: NotifyMethod,
+    /// Shell command run for each reminder when `notify` is `command`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Seconds between checks of the task file.
+    pub interval: u64,
+}
+
+impl Default for ReminderConfig {
+    fn default() -> Self {
+        ReminderConfig {
+            lead_times: vec![LeadTime { minutes: 24 * 60 }, LeadTime { minutes: 60 }],
+            day_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default(),
+            notify: NotifyMethod::default(),
+            command: None,
+            interval: 60,
+        }
+    }
+}
+
+impl ReminderConfig {
+    /// When the reminders of `task` go off, earliest first. These are its
+    /// own reminders, or the configured lead times if it has none.
+    pub fn schedule(&self, task: &Task) -> Vec<(Reminder, DateTime<Local>)> {
+        let reminders: Vec<Reminder> = if task.reminders.is_empty() {
+            self.lead_times
+                .iter()
+                .copied()
+                .map(Reminder::Before)
+                .collect()
+        } else {
+            task.reminders.clone()
+        };
+
+        let mut schedule: Vec<(Reminder, DateTime<Local>)> = reminders
+            .into_iter()
+            .filter_map(|r| Some((r, r.fire_at(task.date_deadline, self.day_time)?)))
+            .collect();
+        schedule.sort_by_key(|(_, at)| *at);
+        schedule
+    }
+}
+
+/// A reminder that already went off. Changing the deadline makes its
+/// reminders go off again.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+struct Fired {
+    id: u32,
+    deadline: Option<Deadline>,
+    reminder: Reminder,
+}
+
+fn plural(n: i64, unit: &str) -> String {
+    if n == 1 {
+        format!("1 {unit}")
+    } else {
+        format!("{n} {unit}s")
+    }
+}
+
+/// "is due tomorrow", "is due in 3 hours", "was due 10 minutes ago"...
+fn describe_due(deadline: Deadline, due: DateTime<Local>, now: DateTime<Local>) -> String {
+    let days = deadline.days_left(now);
+    if let Deadline::Day(_) = deadline {
+        return match days {
+            0 => "is due today".to_string(),
+            1 => "is due tomorrow".to_string(),
+            -1 => "was due yesterday".to_string(),
+            d if d < 0 => format!("was due {} ago", plural(-d, "day")),
+            d => format!("is due in {}", plural(d, "day")),
+        };
+    }
+
+    // Rounded to the nearest minute, checks run a few seconds late.
+    let minutes = (due.signed_duration_since(now).num_seconds() + 30).div_euclid(60);
+    if days > 0 {
+        format!("is due in {}", plural(days, "day"))
+    } else if minutes >= 60 && minutes % 60 == 0 {
+        format!("is due in {}", plural(minutes / 60, "hour"))
+    } else if minutes >= 60 {
+        format!(
+            "is due in {} {}",
+            plural(minutes / 60, "hour"),
+            plural(minutes % 60, "minute")
+        )
+    } else if minutes > 0 {
+        format!("is due in {}", plural(minutes, "minute"))
+    } else if minutes < 0 {
+        format!("was due {} ago", plural(-minutes, "minute"))
+    } else {
+        "is due now".to_string()
+    }
+}
+
+/// Fires reminders for upcoming deadlines and remembers which it already
+/// fired in `tasks.json.reminders`, so restarting doesn't repeat them.
+pub struct Watcher {
+    config: ReminderConfig,
+    state_path: PathBuf,
+    fired: Vec<Fired>,
+}
+
+impl Watcher {
+    pub fn new(config: ReminderConfig, task_file: &Path) -> Self {
+        let mut state_path = task_file.to_path_buf().into_os_string();
+        state_path.push(".reminders");
+        let state_path = PathBuf::from(state_path);
+
+        let fired = match fs::read_to_string(&state_path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                eprintln!(
+                    "{} is not a valid reminder file, starting over: {e}",
+                    state_path.display()
+                );
+                vec![]
+            }),
+            Err(_) => vec![],
+        };
+
+        Watcher {
+            config,
+            state_path,
+            fired,
+        }
+    }
+
+    fn save(&self) -> Result<(), TaskError> {
+        let contents = serde_json::to_string_pretty(&self.fired)?;
+        fs::write(&self.state_path, contents).map_err(|e| TaskError::io(&self.state_path, e))
+    }
+
+    /// Reminders of `task` that are due at `now` and haven't gone off yet.
+    fn pending(&self, task: &Task, now: DateTime<Local>) -> Vec<Fired> {
+        if task.status == TaskStatus::Complete {
+            return vec![];
+        }
+        let due = task
+            .date_deadline
+            .and_then(|d| d.due_at(self.config.day_time));
+
+        self.config
+            .schedule(task)
+            .into_iter()
+            .filter(|(_, at)| {
+                let until = due.map_or(*at, |due| due.max(*at));
+                *at <= now
+                    && until
+                        .checked_add_signed(Duration::minutes(GRACE_MINUTES))
+                        .is_none_or(|until| now <= until)
+            })
+            .map(|(reminder, _)| Fired {
+                id: task.id,
+                deadline: task.date_deadline,
+                reminder,
+            })
+            .filter(|r| !self.fired.contains(r))
+            .collect()
+    }
+
+    /// Fires whatever is due in `tasks` at `now`. Several reminders of the
+    /// same task falling due at once only notify once.
+    pub fn check(&mut self, tasks: &[Task], now: DateTime<Local>) -> Result<(), TaskError> {
+        let before = self.fired.len();
+
+        // Forget reminders of tasks that are gone or have a new deadline.
+        self.fired.retain(|r| {
+            tasks
+                .iter()
+                .any(|t| t.id == r.id && t.date_deadline == r.deadline)
+        });
+        let mut changed = self.fired.len() != before;
+
+        for task in tasks {
+            let pending = self.pending(task, now);
+            if pending.is_empty() {
+                continue;
+            }
+            let due = task
+                .date_deadline
+                .and_then(|d| Some((d, d.due_at(self.config.day_time)?)));
+            let message = match due {
+                Some((deadline, due)) => {
+                    format!("{} {}", task.name, describe_due(deadline, due, now))
+                }
+                None => format!("Reminder: {}", task.name),
+            };
+            self.notify(task, &message);
+            self.fired.extend(pending);
+            changed = true;
+        }
+
+        if changed {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn notify(&self, task: &Task, message: &str) {
+        if self.config.notify == NotifyMethod::Bell {
+            print!("\x07");
+        }
+        println!("{} {message}", Local::now().format("%Y-%m-%d %H:%M"));
+
+        match self.config.notify {
+            NotifyMethod::Desktop => {
+                let shown = Notification::new()
+                    .appname("forgetful-me")
+                    .summary("Forgetful Me")
+                    .body(message)
+                    .show();
+                if let Err(e) = shown {
+                    eprintln!("Couldn't show a desktop notification: {e}");
+                }
+            }
+            NotifyMethod::Bell => {}
+            NotifyMethod::Command => {
+                let Some(command) = &self.config.command else {
+                    return;
+                };
+                let (shell, flag) = if cfg!(windows) {
+                    ("cmd", "/C")
+                } else {
+                    ("sh", "-c")
+                };
+                let deadline = task
+                    .date_deadline
+                    .map(|d| d.to_string())
+                    .unwrap_or_default();
+                let status = process::Command::new(shell)
+                    .arg(flag)
+                    .arg(command)
+                    .env("FORGETFUL_ME_ID", task.id.to_string())
+                    .env("FORGETFUL_ME_NAME", &task.name)
+                    .env("FORGETFUL_ME_DEADLINE", deadline)
+                    .env("FORGETFUL_ME_MESSAGE", message)
+                    .status();
+                match status {
+                    Ok(s) if !s.success() => eprintln!("Reminder command failed: {s}"),
+                    Err(e) => eprintln!("Couldn't run the reminder command: {e}"),
+                    Ok(_) => {}
+                }
+            }
+        }
+    }
+}
+
+/// Checks for due reminders every `interval` seconds until killed, or just
+/// once with `once`.
+pub fn watch(tm: &mut TaskManager, watcher: &mut Watcher, once: bool) -> Result<(), TaskError> {
+    loop {
+        watcher.check(&tm.get_tasklist(), Local::now())?;
+        if once {
+            return Ok(());
+        }
+
+        thread::sleep(std::time::Duration::from_secs(
+            watcher.config.interval.max(1),
+        ));
+        // The file may be mid-edit or broken for a while, keep watching.
+        if let Err(e) = tm.refresh() {
+            eprintln!("forgetful-me: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn lead(s: &str) -> LeadTime {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn lead_times_parse_every_unit() {
+        assert_eq!(lead("1w").minutes, 7 * 24 * 60);
+        assert_eq!(lead("2d").minutes, 2 * 24 * 60);
+        assert_eq!(lead("3H").minutes, 3 * 60);
+        assert_eq!(lead(" 30m ").minutes, 30);
+    }
+
+    #[test]
+    fn lead_times_display_in_the_largest_unit() {
+        assert_eq!(lead("1440m").to_string(), "1d");
+        assert_eq!(lead("14d").to_string(), "2w");
+        assert_eq!(lead("90m").to_string(), "90m");
+        assert_eq!(lead("0h").to_string(), "0m");
+    }
+
+    #[test]
+    fn invalid_lead_times_are_rejected() {
+        for s in ["", "d", "1", "1x", "-1d", "1.5h", "1 day"] {
+            assert!(s.parse::<LeadTime>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn lead_times_beyond_range_are_rejected() {
+        assert!("99999999999999999w".parse::<LeadTime>().is_err());
+        assert!(format!("{}m", i64::MAX).parse::<LeadTime>().is_err());
+    }
+
+    #[test]
+    fn reminders_parse_every_form() {
+        assert_eq!("2d".parse(), Ok(Reminder::Before(lead("2d"))));
+        assert_eq!(
+            "09:00".parse(),
+            Ok(Reminder::DayOf {
+                days_before: 0,
+                time: time(9, 0)
+            })
+        );
+        assert_eq!(
+            "morning".parse(),
+            Ok(Reminder::DayOf {
+                days_before: 0,
+                time: time(9, 0)
+            })
+        );
+        assert_eq!(
+            "1d@18:00".parse(),
+            Ok(Reminder::DayOf {
+                days_before: 1,
+                time: time(18, 0)
+            })
+        );
+        assert_eq!(
+            "2@6pm".parse(),
+            Ok(Reminder::DayOf {
+                days_before: 2,
+                time: time(18, 0)
+            })
+        );
+        let on = NaiveDate::from_ymd_opt(2026, 11, 1).unwrap();
+        assert_eq!(
+            "2026-11-01 10:00".parse(),
+            Ok(Reminder::On(Deadline::at_local(on, time(10, 0)).unwrap()))
+        );
+        assert!("x@18:00".parse::<Reminder>().is_err());
+        assert!("1d@25:00".parse::<Reminder>().is_err());
+    }
+
+    #[test]
+    fn reminders_fire_relative_to_the_deadline() {
+        let day = NaiveDate::from_ymd_opt(2026, 11, 10).unwrap();
+        let deadline = Some(Deadline::Day(day));
+        let at = |d: NaiveDate, t| Local.from_local_datetime(&d.and_time(t)).earliest();
+
+        assert_eq!(
+            Reminder::Before(lead("1d")).fire_at(deadline, time(9, 0)),
+            at(day.pred_opt().unwrap(), time(9, 0))
+        );
+        assert_eq!(
+            Reminder::DayOf {
+                days_before: 1,
+                time: time(18, 0)
+            }
+            .fire_at(deadline, time(9, 0)),
+            at(day.pred_opt().unwrap(), time(18, 0))
+        );
+        assert_eq!(Reminder::Before(lead("1d")).fire_at(None, time(9, 0)), None);
+    }
+
+    #[test]
+    fn reminders_out_of_range_dont_fire() {
+        let deadline = Some(Deadline::Day(
+            NaiveDate::from_ymd_opt(2026, 11, 10).unwrap(),
+        ));
+        assert_eq!(
+            Reminder::Before(lead("99999999w")).fire_at(deadline, time(9, 0)),
+            None
+        );
+        assert_eq!(
+            Reminder::DayOf {
+                days_before: u32::MAX,
+                time: time(9, 0)
+            }
+            .fire_at(deadline, time(9, 0)),
+            None
+        );
+    }
+}
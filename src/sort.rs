@@ -0,0 +1,85 @@
+use std::cmp::Ordering;
+
+use clap::ValueEnum;
+
+use serde::{Deserialize, Serialize};
+
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+use crate::deadline::Deadline;
+use crate::Task;
+use crate::TaskStatus;
+
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq, Eq,
+)]
+pub enum SortKey {
+    #[strum(to_string = "ID")]
+    Id,
+    Deadline,
+    #[strum(to_string = "Date Posted")]
+    Posted,
+    Name,
+    Priority,
+    Status,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 6] = [
+        SortKey::Id,
+        SortKey::Deadline,
+        SortKey::Posted,
+        SortKey::Name,
+        SortKey::Priority,
+        SortKey::Status,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Sort {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl Default for Sort {
+    fn default() -> Self {
+        Sort {
+            key: SortKey::Priority,
+            descending: true,
+        }
+    }
+}
+
+impl Sort {
+    /// Sorts `tasks` in place. The sort is stable, so tasks that compare
+    /// equal keep their list order. Tasks without a deadline always come
+    /// last when sorting by deadline.
+    pub fn apply(self, tasks: &mut [Task]) {
+        // Whole-day deadlines sort after timed ones on the same day.
+        let deadline_key = |d: Deadline| (d.date(), d.time().is_none(), d.time());
+
+        tasks.sort_by(|a, b| {
+            let ordering = match self.key {
+                SortKey::Deadline => match (a.date_deadline, b.date_deadline) {
+                    (None, None) => return Ordering::Equal,
+                    (None, Some(_)) => return Ordering::Greater,
+                    (Some(_), None) => return Ordering::Less,
+                    (Some(a), Some(b)) => deadline_key(a).cmp(&deadline_key(b)),
+                },
+                SortKey::Id => a.id.cmp(&b.id),
+                SortKey::Posted => a.date_posted.cmp(&b.date_posted),
+                SortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                SortKey::Priority => a.priority.cmp(&b.priority),
+                SortKey::Status => {
+                    (a.status == TaskStatus::Complete).cmp(&(b.status == TaskStatus::Complete))
+                }
+            };
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+}
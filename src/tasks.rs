@@ -1,194 +1,1419 @@
-use std::env;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::fs;
 use std::fs::File;
 use std::fs::OpenOptions;
+use std::hash::{Hash, Hasher};
 use std::io::Read;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 use serde_json::json;
 use serde_json::Value;
 
 use strum_macros::Display;
 use strum_macros::EnumString;
 
+use chrono::DateTime;
+use chrono::Days;
+use chrono::Local;
+use chrono::NaiveDate;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::deadline::Deadline;
+use crate::error::TaskError;
+use crate::filter::Filter;
+use crate::recurrence::Recurrence;
+use crate::reminders::Reminder;
+use crate::urgency::UrgencyScale;
+
+pub const TITLE_CHAR_LIMIT: usize = 30;
+pub const DESCRIPTION_CHAR_LIMIT: usize = 100;
+
+/// Length of `text` in user-perceived characters (grapheme clusters), which
+/// is what the character limits count.
+pub fn text_len(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Display, EnumString, PartialEq)]
 pub enum TaskStatus {
     Incomplete,
     Complete,
 }
 
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Critical,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 4] = [
+        Priority::Low,
+        Priority::Medium,
+        Priority::High,
+        Priority::Critical,
+    ];
+}
+
+/// One step of a task's checklist.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChecklistItem {
+    pub name: String,
+    pub done: bool,
+}
+
+impl fmt::Display for ChecklistItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let check = if self.done { "[x]" } else { "[ ]" };
+        write!(f, "{check} {}", self.name)
+    }
+}
+
+// Save files from before typed dates stored "no deadline" as this string.
+const LEGACY_NO_DEADLINE: &str = "0000-00-00";
+
+fn deserialize_deadline<'de, D>(deserializer: D) -> Result<Option<Deadline>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(s) if s == LEGACY_NO_DEADLINE => Ok(None),
+        Some(s) => s.parse().map(Some).map_err(de::Error::custom),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
     pub id: u32,
     pub name: String,
     pub description: String,
+    /// Longer free-form text, possibly spanning several lines.
+    #[serde(default)]
+    pub notes: String,
     pub status: TaskStatus,
-    pub date_posted: String,
-    pub date_deadline: String,
+    #[serde(default)]
+    pub priority: Priority,
+    pub date_posted: NaiveDate,
+    #[serde(deserialize_with = "deserialize_deadline")]
+    pub date_deadline: Option<Deadline>,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
+    /// Ids of tasks that have to be completed first.
+    #[serde(default)]
+    pub blocked_by: Vec<u32>,
+    #[serde(default)]
+    pub reminders: Vec<Reminder>,
+    /// Deadline before the first snooze.
+    #[serde(default)]
+    pub original_deadline: Option<Deadline>,
+    /// How many times the task was snoozed.
+    #[serde(default)]
+    pub deferrals: u32,
+    /// Tasks without a deadline are snoozed by hiding them until this day.
+    #[serde(default)]
+    pub snoozed_until: Option<NaiveDate>,
+    /// Day the task was last completed. Unknown for tasks completed by older versions.
+    #[serde(default)]
+    pub date_completed: Option<NaiveDate>,
+}
+
+impl Task {
+    /// Splits comma separated tags, dropping blanks, leading `#` and duplicates.
+    pub fn parse_tags(input: &str) -> Vec<String> {
+        let mut tags: Vec<String> = vec![];
+        for tag in input.split(',') {
+            let tag = tag.trim().trim_start_matches('#').trim();
+            if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+        tags
+    }
+
+    /// Band of `scale` the deadline falls in, `None` without a deadline.
+    pub fn urgency(&self, now: DateTime<Local>, scale: &UrgencyScale) -> Option<usize> {
+        self.date_deadline
+            .and_then(|d| scale.classify(d.days_left(now)))
+    }
+
+    /// Done and total checklist items, `None` without a checklist.
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        if self.checklist.is_empty() {
+            return None;
+        }
+        let done = self.checklist.iter().filter(|i| i.done).count();
+        Some((done, self.checklist.len()))
+    }
+
+    /// Whether a snooze still hides the task on `today`.
+    pub fn is_snoozed(&self, today: NaiveDate) -> bool {
+        self.snoozed_until.is_some_and(|d| d > today)
+    }
+
+    /// Day a snooze counts from: the deadline, the end of an earlier
+    /// snooze or `today`, whichever is latest.
+    pub fn snooze_base(&self, today: NaiveDate) -> NaiveDate {
+        [self.date_deadline.map(|d| d.date()), self.snoozed_until]
+            .into_iter()
+            .flatten()
+            .fold(today, NaiveDate::max)
+    }
+
+    /// "2 times, originally due 2026-10-10", `None` if never snoozed.
+    pub fn snooze_summary(&self) -> Option<String> {
+        let times = match self.deferrals {
+            0 => return None,
+            1 => "1 time".to_string(),
+            n => format!("{n} times"),
+        };
+        Some(match self.original_deadline {
+            Some(d) => format!("{times}, originally due {d}"),
+            None => times,
+        })
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    pub fn in_project(&self, project: &str) -> bool {
+        self.project
+            .as_ref()
+            .is_some_and(|p| p.eq_ignore_ascii_case(project))
+    }
+
+    /// Builds the next occurrence of a recurring task, taking over its recurrence rule.
+    fn next_occurrence(&mut self, id: u32) -> Option<Task> {
+        let deadline = self.date_deadline?;
+        let today = Local::now().date_naive();
+        // No next occurrence if it would fall past the last representable date.
+        let next = self
+            .recurrence
+            .as_ref()?
+            .next_deadline(deadline.date(), today)?;
+        let recurrence = self.recurrence.take()?;
+
+        Some(Task {
+            id,
+            name: self.name.clone(),
+            description: self.description.clone(),
+            notes: self.notes.clone(),
+            status: TaskStatus::Incomplete,
+            priority: self.priority,
+            date_posted: today,
+            date_deadline: Some(deadline.with_date(next)),
+            recurrence: Some(recurrence),
+            tags: self.tags.clone(),
+            project: self.project.clone(),
+            checklist: self
+                .checklist
+                .iter()
+                .map(|i| ChecklistItem {
+                    done: false,
+                    ..i.clone()
+                })
+                .collect(),
+            blocked_by: self.blocked_by.clone(),
+            // Reminders at a fixed moment stay with this occurrence.
+            reminders: self
+                .reminders
+                .iter()
+                .copied()
+                .filter(|r| r.needs_deadline())
+                .collect(),
+            original_deadline: None,
+            deferrals: 0,
+            snoozed_until: None,
+            date_completed: None,
+        })
+    }
+}
+
+/// How many old copies of the task file are kept around.
+const BACKUP_COUNT: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub modified: Option<DateTime<Local>>,
+    // None if the backup itself can't be read.
+    pub task_count: Option<usize>,
 }
 
 #[derive(Debug)]
 pub struct TaskManager {
     task_list: Vec<Task>,
+    path: PathBuf,
+    // Ids are handed out once from this counter and never reused.
+    id_counter: u32,
+    // Hash of the file contents as we last loaded or saved them.
+    disk_hash: Option<u64>,
 }
 
 impl TaskManager {
-    pub fn new() -> Self {
-        Self { task_list: vec![] }
-    }
-
-    pub fn is_first_task(&mut self) -> bool {
-        self.check_savefile();
-        self.load_file();
-        if self.get_tasklist().len() == 0 {
-            return true;
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            task_list: vec![],
+            path,
+            id_counter: 0,
+            disk_hash: None,
         }
-        return false;
     }
 
-    pub fn get_last_id(&mut self) -> u32 {
-        let mut bigger = 0;
-        for t in self.task_list.iter() {
-            if t.id > bigger {
-                bigger = t.id
-            }
-        }
-        bigger
+    /// Reserves a fresh id. It is persisted with the next save.
+    fn next_id(&mut self) -> u32 {
+        let id = self.id_counter;
+        self.id_counter += 1;
+        id
     }
 
-    pub fn get_id_from_str(&mut self, lines: Vec<String>) -> Vec<u32> {
+    pub fn get_id_from_str(lines: Vec<String>) -> Result<Vec<u32>, TaskError> {
         let mut id_vec: Vec<u32> = vec![];
-        let mut id_str = String::from("");
+        let mut id_str = String::new();
 
         for l in lines {
             for c in l.chars() {
                 if c.is_numeric() {
-                    id_str.push(c.clone());
+                    id_str.push(c);
                 }
 
                 if c.eq(&'\n') {
                     break;
                 }
             }
-            let str_to_u: u32 = id_str.parse().unwrap();
+            let str_to_u: u32 = id_str.parse().map_err(|_| TaskError::InvalidId(l))?;
             id_vec.push(str_to_u);
-            id_str = "".to_string();
+            id_str = String::new();
         }
-        id_vec
+        Ok(id_vec)
     }
 
-    fn update_ids(&mut self) {
-        for i in 0..self.task_list.len() {
-            self.task_list[i].id = i as u32;
+    pub fn get_tasklist(&self) -> Vec<Task> {
+        self.task_list.clone()
+    }
+
+    pub fn get_task(&self, id: u32) -> Option<&Task> {
+        self.task_list.iter().find(|task| task.id == id)
+    }
+
+    /// Tasks matching `filter`, in list order.
+    pub fn get_filtered(&self, filter: &Filter) -> Vec<Task> {
+        let now = Local::now();
+        self.task_list
+            .iter()
+            .filter(|task| filter.matches(task, now))
+            .cloned()
+            .collect()
+    }
+
+    /// Ids of the incomplete tasks `task` is still waiting on.
+    pub fn open_blockers(&self, task: &Task) -> Vec<u32> {
+        task.blocked_by
+            .iter()
+            .copied()
+            .filter(|id| {
+                self.get_task(*id)
+                    .is_some_and(|t| t.status == TaskStatus::Incomplete)
+            })
+            .collect()
+    }
+
+    /// Ids of every task that depends on `id`, directly or through other tasks.
+    pub fn get_dependents(&self, id: u32) -> Vec<u32> {
+        let mut dependents = vec![];
+        let mut queue = vec![id];
+        while let Some(current) = queue.pop() {
+            for task in &self.task_list {
+                if task.blocked_by.contains(&current) && !dependents.contains(&task.id) {
+                    dependents.push(task.id);
+                    queue.push(task.id);
+                }
+            }
         }
+        dependents
     }
 
-    pub fn get_tasklist(&self) -> Vec<Task> {
-        let c = self.task_list.clone();
-        c
+    /// Checks that the tasks `task` depends on exist and that none of them
+    /// depends back on it.
+    fn check_dependencies(&self, task: &Task) -> Result<(), TaskError> {
+        for id in &task.blocked_by {
+            if *id != task.id && self.get_task(*id).is_none() {
+                return Err(TaskError::NotFound(*id));
+            }
+        }
+
+        let mut paths: Vec<Vec<u32>> = task.blocked_by.iter().map(|d| vec![task.id, *d]).collect();
+        let mut seen = vec![];
+        while let Some(path) = paths.pop() {
+            let last = path[path.len() - 1];
+            if last == task.id {
+                return Err(TaskError::DependencyCycle(path));
+            }
+            if seen.contains(&last) {
+                continue;
+            }
+            seen.push(last);
+            if let Some(next) = self.get_task(last) {
+                for id in &next.blocked_by {
+                    let mut longer = path.clone();
+                    longer.push(*id);
+                    paths.push(longer);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Every tag in use, sorted.
+    pub fn get_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = vec![];
+        for task in &self.task_list {
+            for tag in &task.tags {
+                if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    tags.push(tag.clone());
+                }
+            }
+        }
+        tags.sort_by_key(|t| t.to_lowercase());
+        tags
+    }
+
+    /// Every project in use, sorted.
+    pub fn get_projects(&self) -> Vec<String> {
+        let mut projects: Vec<String> = vec![];
+        for task in &self.task_list {
+            if let Some(project) = &task.project {
+                if !projects.iter().any(|p| p.eq_ignore_ascii_case(project)) {
+                    projects.push(project.clone());
+                }
+            }
+        }
+        projects.sort_by_key(|p| p.to_lowercase());
+        projects
+    }
+
+    /// Takes the advisory lock that serializes every read-modify-write of
+    /// the task file between sessions. Released when the returned file drops.
+    fn lock(&self) -> Result<File, TaskError> {
+        let lock_path = self.sibling_path(".lock");
+        if let Some(dir) = lock_path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|e| TaskError::io(dir, e))?;
+        }
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .map_err(|e| TaskError::io(&lock_path, e))?;
+        file.lock().map_err(|e| TaskError::io(&lock_path, e))?;
+        Ok(file)
     }
 
-    fn get_path(&mut self) -> String {
-        let mut f = env::current_exe().expect("Couldn't find exe");
-        f.pop();
-        f.push("tasks.json");
-        f.into_os_string().into_string().unwrap()
+    fn content_hash(content: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        hasher.finish()
     }
 
-    fn load_file(&mut self) {
-        let p = self.get_path();
-        let mut file = File::open(p).expect("Failed to load file.");
+    /// Reloads the task list if the file on disk is no longer what we last
+    /// loaded or saved, i.e. another session wrote to it in the meantime.
+    fn reload_if_changed(&mut self) -> Result<(), TaskError> {
+        let Ok(content) = fs::read_to_string(&self.path) else {
+            // Gone or unreadable, the next save writes it again.
+            return Ok(());
+        };
+        if Some(Self::content_hash(&content)) != self.disk_hash {
+            self.load_file()?;
+        }
+        Ok(())
+    }
+
+    /// Runs `change` against the latest task list while holding the lock, then saves.
+    fn modify<T>(
+        &mut self,
+        change: impl FnOnce(&mut Self) -> Result<T, TaskError>,
+    ) -> Result<T, TaskError> {
+        let _lock = self.lock()?;
+        self.check_savefile()?;
+        self.reload_if_changed()?;
+        let out = change(self)?;
+        self.save_tofile()?;
+        Ok(out)
+    }
+
+    /// Reads a task file, returning its tasks and the next free id.
+    fn read_file(path: &Path) -> Result<(Vec<Task>, u32, String), TaskError> {
+        let mut file = File::open(path).map_err(|e| TaskError::io(path, e))?;
 
         let mut f_content = String::new();
-        file.read_to_string(&mut f_content).unwrap();
+        file.read_to_string(&mut f_content)
+            .map_err(|e| TaskError::io(path, e))?;
 
-        let data: Value = serde_json::from_str(&f_content).unwrap();
+        let corrupt = |source| TaskError::Corrupt {
+            path: path.to_path_buf(),
+            source,
+        };
 
-        self.task_list = serde_json::from_value(data["task-list"].clone()).unwrap_or_default();
+        let data: Value = serde_json::from_str(&f_content).map_err(corrupt)?;
+
+        let task_list: Vec<Task> =
+            serde_json::from_value(data["task-list"].clone()).map_err(corrupt)?;
+
+        // Files written before the counter existed fall back to the highest id in use.
+        let first_free = task_list.iter().map(|t| t.id + 1).max().unwrap_or(0);
+        let saved_counter = data["next-id"]
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .unwrap_or(0);
+
+        Ok((task_list, saved_counter.max(first_free), f_content))
     }
 
-    fn check_savefile(&mut self) {
-        let p = self.get_path();
+    fn load_file(&mut self) -> Result<(), TaskError> {
+        let (task_list, id_counter, f_content) = Self::read_file(&self.path)?;
+        self.task_list = task_list;
+        self.id_counter = id_counter;
+        self.disk_hash = Some(Self::content_hash(&f_content));
 
-        let fpath = Path::new(&p);
+        // Rewrite old save files so the sentinel deadline doesn't linger on disk.
+        if f_content.contains(&format!("\"{LEGACY_NO_DEADLINE}\"")) {
+            self.save_tofile()?;
+        }
+        Ok(())
+    }
 
-        if !fpath.exists() {
-            File::create(fpath).unwrap();
-            self.save_tofile();
+    fn check_savefile(&mut self) -> Result<(), TaskError> {
+        if !self.path.exists() {
+            if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                fs::create_dir_all(dir).map_err(|e| TaskError::io(dir, e))?;
+            }
+            self.save_tofile()?;
         }
+        Ok(())
     }
 
-    fn save_tofile(&mut self) {
-        let p = self.get_path();
-        let savjson: serde_json::Value;
+    /// `tasks.json` -> `tasks.json<suffix>`, in the same directory.
+    fn sibling_path(&self, suffix: &str) -> PathBuf {
+        let mut p = self.path.clone().into_os_string();
+        p.push(suffix);
+        PathBuf::from(p)
+    }
 
-        savjson = json!({
-            "task-list":self.task_list
-        });
+    fn backup_path(&self, n: usize) -> PathBuf {
+        self.sibling_path(&format!(".bak.{n}"))
+    }
 
-        let pretty_savjson = serde_json::to_string_pretty(&savjson).unwrap();
+    /// Shifts `.bak.1..N` up by one and copies the current file to `.bak.1`.
+    fn rotate_backups(&self) -> Result<(), TaskError> {
+        if !self.path.exists() {
+            return Ok(());
+        }
 
+        for n in (1..BACKUP_COUNT).rev() {
+            let from = self.backup_path(n);
+            if from.exists() {
+                let to = self.backup_path(n + 1);
+                fs::rename(&from, &to).map_err(|e| TaskError::io(&from, e))?;
+            }
+        }
+
+        let newest = self.backup_path(1);
+        fs::copy(&self.path, &newest).map_err(|e| TaskError::io(&newest, e))?;
+        Ok(())
+    }
+
+    /// Writes `content` to `path` and flushes it to disk.
+    fn write_synced(path: &Path, content: &str) -> Result<(), TaskError> {
         let mut file = OpenOptions::new()
             .write(true)
+            .create(true)
             .truncate(true)
-            .open(p)
-            .expect("Err");
+            .open(path)
+            .map_err(|e| TaskError::io(path, e))?;
 
-        file.write_all(&pretty_savjson.into_bytes()).unwrap();
+        file.write_all(content.as_bytes())
+            .and_then(|()| file.sync_all())
+            .map_err(|e| TaskError::io(path, e))
     }
 
-    pub fn save_task(&mut self, task: Task) {
-        self.check_savefile();
-        self.task_list.push(task);
-        self.save_tofile();
+    /// Makes renames in the task file's directory durable.
+    /// Not every platform can open a directory.
+    fn sync_dir(&self) {
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            if let Ok(dir) = File::open(dir) {
+                let _ = dir.sync_all();
+            }
+        }
     }
 
-    pub fn load(&mut self) {
-        self.check_savefile();
-        self.load_file();
+    /// Writes the task list to a temporary file, flushes it to disk and
+    /// renames it over the real one, so a crash never leaves a half-written file.
+    fn save_tofile(&mut self) -> Result<(), TaskError> {
+        let savjson = json!({
+            "next-id":self.id_counter,
+            "task-list":self.task_list
+        });
+
+        let pretty_savjson = serde_json::to_string_pretty(&savjson)?;
+        let hash = Self::content_hash(&pretty_savjson);
+
+        let tmp_path = self.sibling_path(".tmp");
+        Self::write_synced(&tmp_path, &pretty_savjson)?;
+
+        self.rotate_backups()?;
+
+        fs::rename(&tmp_path, &self.path).map_err(|e| TaskError::io(&self.path, e))?;
+        self.disk_hash = Some(hash);
+
+        self.sync_dir();
+        Ok(())
     }
 
-    pub fn delete_tasks(&mut self, rm_list: Vec<u32>) {
-        for id_num in rm_list.iter() {
-            let _ = &self.task_list.retain(|task| {
-                if task.id.eq(id_num) == true {
-                    return false;
+    /// Completed tasks moved out of the task list are kept in `tasks.json.archive`.
+    fn archive_path(&self) -> PathBuf {
+        self.sibling_path(".archive")
+    }
+
+    /// Replaces the archive the same way [`Self::save_tofile`] replaces the task file.
+    fn save_archive(&self, archive: &[Task]) -> Result<(), TaskError> {
+        let pretty_savjson = serde_json::to_string_pretty(&json!({ "task-list": archive }))?;
+
+        let tmp_path = self.sibling_path(".archive.tmp");
+        Self::write_synced(&tmp_path, &pretty_savjson)?;
+
+        let archive_path = self.archive_path();
+        fs::rename(&tmp_path, &archive_path).map_err(|e| TaskError::io(&archive_path, e))?;
+        self.sync_dir();
+        Ok(())
+    }
+
+    /// Archived tasks, in the order they were archived.
+    pub fn get_archive(&self) -> Result<Vec<Task>, TaskError> {
+        let archive_path = self.archive_path();
+        if !archive_path.exists() {
+            return Ok(vec![]);
+        }
+        Self::read_file(&archive_path).map(|(archive, _, _)| archive)
+    }
+
+    /// Whether `task` was completed at least `days` days ago. Tasks completed
+    /// before completion days were recorded count as old enough.
+    fn archivable(task: &Task, days: u64, today: NaiveDate) -> bool {
+        task.status == TaskStatus::Complete
+            && task.date_completed.is_none_or(|done| {
+                done.checked_add_days(Days::new(days))
+                    .is_some_and(|d| d <= today)
+            })
+    }
+
+    /// Moves the tasks completed at least `days` days ago from the task list
+    /// to the archive, so `0` archives every completed task. Returns their ids.
+    pub fn archive_completed(&mut self, days: u64) -> Result<Vec<u32>, TaskError> {
+        let today = Local::now().date_naive();
+        // Don't save (and rotate the backups) when there is nothing to do.
+        if !self
+            .task_list
+            .iter()
+            .any(|t| Self::archivable(t, days, today))
+        {
+            return Ok(vec![]);
+        }
+
+        self.modify(|tm| {
+            let (archived, kept): (Vec<Task>, Vec<Task>) = tm
+                .task_list
+                .iter()
+                .cloned()
+                .partition(|t| Self::archivable(t, days, today));
+            let ids: Vec<u32> = archived.iter().map(|t| t.id).collect();
+            if ids.is_empty() {
+                return Ok(ids);
+            }
+
+            // Saved before the task file, so a failure leaves a task in both
+            // files rather than in neither.
+            let mut archive = tm.get_archive()?;
+            archive.extend(archived);
+            tm.save_archive(&archive)?;
+
+            tm.task_list = kept;
+            // Archived tasks are complete, so nothing waits on them any more.
+            for task in &mut tm.task_list {
+                task.blocked_by.retain(|id| !ids.contains(id));
+            }
+            Ok(ids)
+        })
+    }
+
+    /// Moves tasks from the archive back into the task list. Returns the ids
+    /// they have there, which are new for tasks whose id got taken meanwhile.
+    pub fn restore_archived(&mut self, ids: &[u32]) -> Result<Vec<u32>, TaskError> {
+        let _lock = self.lock()?;
+        self.check_savefile()?;
+        self.reload_if_changed()?;
+
+        let archive = self.get_archive()?;
+        if let Some(id) = ids.iter().find(|id| !archive.iter().any(|t| t.id == **id)) {
+            return Err(TaskError::NotFound(*id));
+        }
+
+        let mut restored = vec![];
+        let mut kept = vec![];
+        for mut task in archive {
+            if !ids.contains(&task.id) {
+                kept.push(task);
+                continue;
+            }
+            match self.get_task(task.id) {
+                // A restored backup already brought the task back, keep the
+                // archived copy rather than lose anything.
+                Some(live) if live.name == task.name && live.date_posted == task.date_posted => {
+                    kept.push(task);
+                    continue;
                 }
-                return true;
+                Some(_) => task.id = self.next_id(),
+                None => {}
+            }
+            // Dependencies archived or deleted in the meantime are dropped.
+            task.blocked_by
+                .retain(|id| self.task_list.iter().any(|t| t.id == *id));
+            restored.push(task.id);
+            self.task_list.push(task);
+        }
+
+        // The opposite order of archiving, for the same reason.
+        self.save_tofile()?;
+        self.save_archive(&kept)?;
+        Ok(restored)
+    }
+
+    /// Backups of the task file, newest first.
+    pub fn get_backups(&self) -> Vec<Backup> {
+        let mut backups = vec![];
+        for n in 1..=BACKUP_COUNT {
+            let path = self.backup_path(n);
+            let Ok(meta) = fs::metadata(&path) else {
+                continue;
+            };
+            backups.push(Backup {
+                modified: meta.modified().ok().map(DateTime::<Local>::from),
+                task_count: Self::read_file(&path).ok().map(|(t, _, _)| t.len()),
+                path,
             });
         }
+        backups
+    }
+
+    /// Replaces the task list with the contents of a backup.
+    /// The current list is backed up first, so a restore can be undone.
+    pub fn restore_backup(&mut self, backup: &Backup) -> Result<(), TaskError> {
+        let (task_list, id_counter, _) = Self::read_file(&backup.path)?;
+        self.modify(|tm| {
+            tm.task_list = task_list;
+            // Never hand out an id again that the current file already used.
+            tm.id_counter = tm.id_counter.max(id_counter);
+            Ok(())
+        })
+    }
+
+    /// Adds a task, giving it a fresh id. Returns the id.
+    pub fn save_task(&mut self, mut task: Task) -> Result<u32, TaskError> {
+        self.modify(|tm| {
+            task.id = tm.next_id();
+            tm.check_dependencies(&task)?;
+            let id = task.id;
+            tm.task_list.push(task);
+            Ok(id)
+        })
+    }
+
+    pub fn load(&mut self) -> Result<(), TaskError> {
+        let _lock = self.lock()?;
+        self.check_savefile()?;
+        self.load_file()
+    }
+
+    /// Picks up changes another session saved since we last read or wrote the file.
+    pub fn refresh(&mut self) -> Result<(), TaskError> {
+        let _lock = self.lock()?;
+        self.reload_if_changed()
+    }
 
-        self.update_ids();
+    /// Moves an unreadable task file aside and starts over with an empty list.
+    /// Returns where the old file was moved to.
+    pub fn backup_and_reset(&mut self) -> Result<PathBuf, TaskError> {
+        let _lock = self.lock()?;
+        let backup =
+            self.sibling_path(&format!(".corrupt-{}", Local::now().format("%Y%m%d%H%M%S")));
 
-        self.check_savefile();
-        self.save_tofile();
+        fs::rename(&self.path, &backup).map_err(|e| TaskError::io(&self.path, e))?;
+
+        self.task_list = vec![];
+        // Ids from the old file live on in the archive and the backups, so
+        // never hand them out again.
+        self.id_counter = self.id_counter.max(self.first_unused_id());
+        self.check_savefile()?;
+        Ok(backup)
+    }
+
+    /// Lowest id that neither the archive nor any backup has used.
+    fn first_unused_id(&self) -> u32 {
+        let archived = self
+            .get_archive()
+            .unwrap_or_default()
+            .iter()
+            .map(|t| t.id + 1)
+            .max()
+            .unwrap_or(0);
+        let backed_up = (1..=BACKUP_COUNT)
+            .filter_map(|n| Self::read_file(&self.backup_path(n)).ok())
+            .map(|(_, id_counter, _)| id_counter)
+            .max()
+            .unwrap_or(0);
+        archived.max(backed_up)
     }
 
-    pub fn switch_task_status(&mut self, switch_list: Vec<u32>) {
-        for id_num in switch_list.iter() {
-            if self.task_list[*id_num as usize].id.eq(id_num) == true {
-                if self.task_list[*id_num as usize].status == TaskStatus::Complete {
-                    self.task_list[*id_num as usize].status = TaskStatus::Incomplete
-                } else if self.task_list[*id_num as usize].status == TaskStatus::Incomplete {
-                    self.task_list[*id_num as usize].status = TaskStatus::Complete
+    pub fn delete_tasks(&mut self, rm_list: &[u32]) -> Result<(), TaskError> {
+        self.modify(|tm| {
+            for id_num in rm_list {
+                tm.task_list.retain(|task| !task.id.eq(id_num));
+            }
+            for task in &mut tm.task_list {
+                task.blocked_by.retain(|id| !rm_list.contains(id));
+            }
+            Ok(())
+        })
+    }
+
+    /// Marks the task at `index` complete, queueing the next occurrence if it repeats.
+    fn complete_task(&mut self, index: usize, next_tasks: &mut Vec<Task>) {
+        self.task_list[index].status = TaskStatus::Complete;
+        self.task_list[index].date_completed = Some(Local::now().date_naive());
+        if self.task_list[index].recurrence.is_some() {
+            let next_id = self.next_id();
+            if let Some(next) = self.task_list[index].next_occurrence(next_id) {
+                next_tasks.push(next);
+            }
+        }
+    }
+
+    pub fn switch_task_status(&mut self, switch_list: &[u32]) -> Result<(), TaskError> {
+        self.modify(|tm| {
+            let mut next_tasks = vec![];
+            for id_num in switch_list {
+                if let Some(index) = tm.task_list.iter().position(|t| t.id.eq(id_num)) {
+                    if tm.task_list[index].status == TaskStatus::Complete {
+                        tm.task_list[index].status = TaskStatus::Incomplete;
+                        tm.task_list[index].date_completed = None;
+                    } else if tm.task_list[index].status == TaskStatus::Incomplete {
+                        tm.complete_task(index, &mut next_tasks);
+                    }
                 }
             }
+            tm.task_list.append(&mut next_tasks);
+            Ok(())
+        })
+    }
+
+    /// Applies the changes between `original`, the task as it was read,
+    /// and `edited` to the stored task. Fields the edit left alone keep what
+    /// another session may have saved in the meantime.
+    pub fn update_task(&mut self, original: &Task, edited: Task) -> Result<(), TaskError> {
+        fn take_edited<T: PartialEq>(original: &T, edited: T, stored: &mut T) {
+            if edited != *original {
+                *stored = edited;
+            }
         }
 
-        self.update_ids();
+        self.modify(|tm| {
+            let Some(index) = tm.task_list.iter().position(|t| t.id == edited.id) else {
+                return Err(TaskError::NotFound(edited.id));
+            };
+
+            let mut updated = tm.task_list[index].clone();
+            take_edited(&original.name, edited.name, &mut updated.name);
+            take_edited(
+                &original.description,
+                edited.description,
+                &mut updated.description,
+            );
+            take_edited(&original.notes, edited.notes, &mut updated.notes);
+            take_edited(&original.priority, edited.priority, &mut updated.priority);
+            take_edited(&original.project, edited.project, &mut updated.project);
+            take_edited(&original.tags, edited.tags, &mut updated.tags);
+            take_edited(
+                &original.checklist,
+                edited.checklist,
+                &mut updated.checklist,
+            );
+            take_edited(
+                &original.blocked_by,
+                edited.blocked_by,
+                &mut updated.blocked_by,
+            );
+            take_edited(
+                &original.date_deadline,
+                edited.date_deadline,
+                &mut updated.date_deadline,
+            );
+            take_edited(
+                &original.recurrence,
+                edited.recurrence,
+                &mut updated.recurrence,
+            );
+            take_edited(
+                &original.reminders,
+                edited.reminders,
+                &mut updated.reminders,
+            );
+            take_edited(&original.status, edited.status, &mut updated.status);
+            tm.check_dependencies(&updated)?;
+
+            let becomes_complete = tm.task_list[index].status == TaskStatus::Incomplete
+                && updated.status == TaskStatus::Complete;
+            if updated.status == TaskStatus::Incomplete {
+                updated.date_completed = None;
+            }
+
+            tm.task_list[index] = updated;
+
+            if becomes_complete {
+                let mut next_tasks = vec![];
+                tm.complete_task(index, &mut next_tasks);
+                tm.task_list.append(&mut next_tasks);
+            }
+            Ok(())
+        })
+    }
+
+    /// Puts a task off until `until`: its deadline moves to that day, keeping
+    /// the time of day, and a task without a deadline is hidden until then.
+    pub fn snooze_task(&mut self, id: u32, until: NaiveDate) -> Result<(), TaskError> {
+        self.modify(|tm| {
+            let Some(task) = tm.task_list.iter_mut().find(|t| t.id == id) else {
+                return Err(TaskError::NotFound(id));
+            };
+            if task.status == TaskStatus::Complete {
+                return Err(TaskError::AlreadyComplete(id));
+            }
+            let base = task.snooze_base(Local::now().date_naive());
+            if until <= base {
+                return Err(TaskError::SnoozeTooEarly { id, base });
+            }
+
+            match task.date_deadline {
+                Some(deadline) => {
+                    task.original_deadline.get_or_insert(deadline);
+                    task.date_deadline = Some(deadline.with_date(until));
+                }
+                None => task.snoozed_until = Some(until),
+            }
+            task.deferrals += 1;
+            Ok(())
+        })
+    }
+
+    /// Ticks or unticks the checklist items in `marked`, given with their
+    /// index, leaving the rest as they are stored. With `complete_when_done`
+    /// the task itself is completed once every item is done. Returns whether
+    /// that happened.
+    pub fn set_checklist(
+        &mut self,
+        id: u32,
+        marked: &[(usize, ChecklistItem)],
+        complete_when_done: bool,
+    ) -> Result<bool, TaskError> {
+        self.modify(|tm| {
+            let Some(index) = tm.task_list.iter().position(|t| t.id == id) else {
+                return Err(TaskError::NotFound(id));
+            };
+
+            let task = &mut tm.task_list[index];
+            for (index, mark) in marked {
+                // Items another session removed or moved in the meantime are skipped.
+                if let Some(item) = task
+                    .checklist
+                    .get_mut(*index)
+                    .filter(|i| i.name == mark.name)
+                {
+                    item.done = mark.done;
+                }
+            }
+
+            let all_done = task.checklist.iter().all(|i| i.done);
+            if !(complete_when_done && all_done && task.status == TaskStatus::Incomplete) {
+                return Ok(false);
+            }
+            if !tm.open_blockers(&tm.task_list[index]).is_empty() {
+                return Ok(false);
+            }
+
+            let mut next_tasks = vec![];
+            tm.complete_task(index, &mut next_tasks);
+            tm.task_list.append(&mut next_tasks);
+            Ok(true)
+        })
+    }
+
+    pub fn set_task_status(
+        &mut self,
+        id_list: &[u32],
+        status: &TaskStatus,
+    ) -> Result<(), TaskError> {
+        self.modify(|tm| {
+            let mut next_tasks = vec![];
+            for index in 0..tm.task_list.len() {
+                let task = &mut tm.task_list[index];
+                if !id_list.contains(&task.id) || task.status == *status {
+                    continue;
+                }
+                if *status == TaskStatus::Complete {
+                    tm.complete_task(index, &mut next_tasks);
+                } else {
+                    task.status = status.clone();
+                    task.date_completed = None;
+                }
+            }
+            tm.task_list.append(&mut next_tasks);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn manager() -> (TempDir, TaskManager) {
+        let dir = TempDir::new().unwrap();
+        let mut tm = TaskManager::new(dir.path().join("tasks.json"));
+        tm.load().unwrap();
+        (dir, tm)
+    }
+
+    fn task(name: &str) -> Task {
+        Task {
+            id: 0,
+            name: name.to_string(),
+            description: String::new(),
+            notes: String::new(),
+            status: TaskStatus::Incomplete,
+            priority: Priority::Medium,
+            date_posted: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            date_deadline: None,
+            recurrence: None,
+            tags: vec![],
+            project: None,
+            checklist: vec![],
+            blocked_by: vec![],
+            reminders: vec![],
+            original_deadline: None,
+            deferrals: 0,
+            snoozed_until: None,
+            date_completed: None,
+        }
+    }
 
-        self.check_savefile();
-        self.save_tofile();
+    fn names(tasks: &[Task]) -> Vec<(u32, &str)> {
+        tasks.iter().map(|t| (t.id, t.name.as_str())).collect()
     }
 
-    pub fn get_days_diff(
-        &self,
-        naive_date_a: chrono::NaiveDate,
-        naive_date_b: chrono::NaiveDate,
-    ) -> i64 {
-        let diff_duration = naive_date_a.signed_duration_since(naive_date_b);
-        let days = diff_duration.num_days();
-        days
+    #[test]
+    fn archive_and_restore() {
+        let (_dir, mut tm) = manager();
+        let done = tm.save_task(task("Done")).unwrap();
+        let open = tm.save_task(task("Open")).unwrap();
+        tm.set_task_status(&[done], &TaskStatus::Complete).unwrap();
+
+        assert_eq!(tm.archive_completed(1).unwrap(), Vec::<u32>::new());
+        assert_eq!(tm.archive_completed(0).unwrap(), vec![done]);
+        assert_eq!(names(&tm.get_tasklist()), vec![(open, "Open")]);
+        assert_eq!(names(&tm.get_archive().unwrap()), vec![(done, "Done")]);
+
+        assert_eq!(tm.restore_archived(&[done]).unwrap(), vec![done]);
+        assert!(tm.get_archive().unwrap().is_empty());
+        assert!(tm.get_task(done).is_some());
+        assert!(matches!(
+            tm.restore_archived(&[done]),
+            Err(TaskError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn restoring_onto_a_taken_id_keeps_both_tasks() {
+        let (_dir, mut tm) = manager();
+        let id = tm.save_task(task("Archived")).unwrap();
+        tm.set_task_status(&[id], &TaskStatus::Complete).unwrap();
+        tm.archive_completed(0).unwrap();
+
+        // Another task ends up with the same id, e.g. from an old task file.
+        let mut unrelated = task("Unrelated");
+        unrelated.id = id;
+        tm.modify(|tm| {
+            tm.task_list.push(unrelated);
+            Ok(())
+        })
+        .unwrap();
+
+        let restored = tm.restore_archived(&[id]).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_ne!(restored[0], id);
+        assert_eq!(
+            names(&tm.get_tasklist()),
+            vec![(id, "Unrelated"), (restored[0], "Archived")]
+        );
+        assert!(tm.get_archive().unwrap().is_empty());
+    }
+
+    #[test]
+    fn restoring_a_task_already_back_keeps_the_archived_copy() {
+        let (_dir, mut tm) = manager();
+        let id = tm.save_task(task("Twice")).unwrap();
+        tm.set_task_status(&[id], &TaskStatus::Complete).unwrap();
+        tm.archive_completed(0).unwrap();
+        // The newest backup is the list from before archiving.
+        let backup = tm.get_backups()[0].clone();
+        tm.restore_backup(&backup).unwrap();
+        assert!(tm.get_task(id).is_some());
+
+        assert_eq!(tm.restore_archived(&[id]).unwrap(), Vec::<u32>::new());
+        assert_eq!(tm.get_tasklist().len(), 1);
+        assert_eq!(names(&tm.get_archive().unwrap()), vec![(id, "Twice")]);
+    }
+
+    #[test]
+    fn checklist_marks_match_by_position() {
+        let (_dir, mut tm) = manager();
+        let mut t = task("Steps");
+        t.checklist = vec![
+            ChecklistItem {
+                name: "Same".to_string(),
+                done: true,
+            },
+            ChecklistItem {
+                name: "Same".to_string(),
+                done: false,
+            },
+        ];
+        let id = tm.save_task(t).unwrap();
+
+        let mark = |done| ChecklistItem {
+            name: "Same".to_string(),
+            done,
+        };
+        assert!(tm.set_checklist(id, &[(1, mark(true))], true).unwrap());
+        let t = tm.get_task(id).unwrap();
+        assert!(t.checklist.iter().all(|i| i.done));
+        assert_eq!(t.status, TaskStatus::Complete);
+
+        // A mark for an item that is no longer there is skipped.
+        assert!(!tm.set_checklist(id, &[(2, mark(false))], true).unwrap());
+        assert!(!tm
+            .set_checklist(
+                id,
+                &[(
+                    0,
+                    ChecklistItem {
+                        name: "Other".to_string(),
+                        done: false
+                    }
+                )],
+                true
+            )
+            .unwrap());
+        assert!(tm.get_task(id).unwrap().checklist[0].done);
+    }
+
+    #[test]
+    fn ids_are_never_reused() {
+        let (dir, mut tm) = manager();
+        let first = tm.save_task(task("First")).unwrap();
+        let last = tm.save_task(task("Last")).unwrap();
+        tm.delete_tasks(&[last]).unwrap();
+        let next = tm.save_task(task("Next")).unwrap();
+        assert_eq!((first, last, next), (0, 1, 2));
+
+        // The counter is saved with the list, so other sessions continue it.
+        tm.delete_tasks(&[next]).unwrap();
+        let mut other = TaskManager::new(dir.path().join("tasks.json"));
+        other.load().unwrap();
+        assert_eq!(other.save_task(task("Other")).unwrap(), 3);
+        assert_eq!(
+            names(&other.get_tasklist()),
+            vec![(0, "First"), (3, "Other")]
+        );
+    }
+
+    #[test]
+    fn files_without_a_counter_continue_after_the_highest_id() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tasks.json");
+        let mut old = task("Old");
+        old.id = 5;
+        fs::write(&path, json!({ "task-list": [old] }).to_string()).unwrap();
+
+        let mut tm = TaskManager::new(path);
+        tm.load().unwrap();
+        assert_eq!(tm.save_task(task("New")).unwrap(), 6);
+    }
+
+    #[test]
+    fn ids_are_read_from_menu_labels() {
+        let labels = vec!["ID:12\nName:Pay rent 3".to_string(), "ID:7".to_string()];
+        assert_eq!(TaskManager::get_id_from_str(labels).unwrap(), vec![12, 7]);
+        assert!(TaskManager::get_id_from_str(vec!["Name:x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn legacy_deadlines_are_migrated_on_load() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tasks.json");
+        let legacy = json!({
+            "task-list": [
+                {
+                    "id": 0,
+                    "name": "No deadline",
+                    "description": "",
+                    "status": "Incomplete",
+                    "date_posted": "2023-01-02",
+                    "date_deadline": "0000-00-00"
+                },
+                {
+                    "id": 1,
+                    "name": "Deadline",
+                    "description": "",
+                    "status": "Complete",
+                    "date_posted": "2023-01-02",
+                    "date_deadline": "2023-02-03"
+                }
+            ]
+        });
+        fs::write(&path, legacy.to_string()).unwrap();
+
+        let mut tm = TaskManager::new(path.clone());
+        tm.load().unwrap();
+        assert_eq!(tm.get_task(0).unwrap().date_deadline, None);
+        assert_eq!(
+            tm.get_task(1).unwrap().date_deadline,
+            Some(Deadline::Day(NaiveDate::from_ymd_opt(2023, 2, 3).unwrap()))
+        );
+        assert_eq!(tm.get_task(1).unwrap().priority, Priority::Medium);
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(!saved.contains(LEGACY_NO_DEADLINE));
+        assert!(saved.contains("\"date_deadline\": null"));
+    }
+
+    #[test]
+    fn unreadable_dates_are_reported_as_corrupt() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tasks.json");
+        let mut bad = serde_json::to_value(task("Bad")).unwrap();
+        bad["date_posted"] = json!("yesterday-ish");
+        fs::write(&path, json!({ "task-list": [bad] }).to_string()).unwrap();
+
+        let mut tm = TaskManager::new(path);
+        assert!(matches!(tm.load(), Err(TaskError::Corrupt { .. })));
+    }
+
+    #[test]
+    fn saves_keep_five_rolling_backups() {
+        let (dir, mut tm) = manager();
+        for n in 0..7 {
+            tm.save_task(task(&format!("Task {n}"))).unwrap();
+        }
+
+        let counts: Vec<Option<usize>> = tm.get_backups().iter().map(|b| b.task_count).collect();
+        assert_eq!(counts, vec![Some(6), Some(5), Some(4), Some(3), Some(2)]);
+        assert!(!dir.path().join("tasks.json.bak.6").exists());
+        assert!(!dir.path().join("tasks.json.tmp").exists());
+    }
+
+    #[test]
+    fn restoring_a_backup_can_be_undone() {
+        let (_dir, mut tm) = manager();
+        tm.save_task(task("Kept")).unwrap();
+        tm.save_task(task("Lost")).unwrap();
+        tm.delete_tasks(&[0, 1]).unwrap();
+
+        let backup = tm.get_backups()[0].clone();
+        tm.restore_backup(&backup).unwrap();
+        assert_eq!(names(&tm.get_tasklist()), vec![(0, "Kept"), (1, "Lost")]);
+        // Ids handed out since the backup are still not reused.
+        assert_eq!(tm.save_task(task("New")).unwrap(), 2);
+
+        // The list from before the restore became the newest backup.
+        tm.delete_tasks(&[2]).unwrap();
+        let before_restore = tm.get_backups()[2].clone();
+        assert_eq!(before_restore.task_count, Some(0));
+        tm.restore_backup(&before_restore).unwrap();
+        assert!(tm.get_tasklist().is_empty());
+    }
+
+    #[test]
+    fn unreadable_backups_are_listed_without_a_count() {
+        let (dir, mut tm) = manager();
+        tm.save_task(task("One")).unwrap();
+        fs::write(dir.path().join("tasks.json.bak.1"), "{").unwrap();
+
+        let backups = tm.get_backups();
+        assert_eq!(backups[0].task_count, None);
+        assert!(matches!(
+            tm.restore_backup(&backups[0]),
+            Err(TaskError::Corrupt { .. })
+        ));
+        assert_eq!(tm.get_tasklist().len(), 1);
+    }
+
+    #[test]
+    fn changes_from_other_sessions_are_kept() {
+        let (dir, mut tm) = manager();
+        let mut other = TaskManager::new(dir.path().join("tasks.json"));
+        other.load().unwrap();
+
+        let theirs = other.save_task(task("Theirs")).unwrap();
+        // `tm` still holds the empty list it loaded, saving reloads first.
+        let mine = tm.save_task(task("Mine")).unwrap();
+        assert_ne!(mine, theirs);
+        assert_eq!(
+            names(&tm.get_tasklist()),
+            vec![(theirs, "Theirs"), (mine, "Mine")]
+        );
+
+        other.refresh().unwrap();
+        assert_eq!(other.get_tasklist().len(), 2);
+    }
+
+    #[test]
+    fn edits_only_overwrite_the_fields_that_were_edited() {
+        let (dir, mut tm) = manager();
+        let id = tm.save_task(task("Report")).unwrap();
+        let original = tm.get_task(id).unwrap().clone();
+
+        // Another session completes and retags the task while it is edited.
+        let mut other = TaskManager::new(dir.path().join("tasks.json"));
+        other.load().unwrap();
+        other.set_task_status(&[id], &TaskStatus::Complete).unwrap();
+        let mut retagged = other.get_task(id).unwrap().clone();
+        retagged.tags = vec!["theirs".to_string()];
+        retagged.priority = Priority::Low;
+        let before = other.get_task(id).unwrap().clone();
+        other.update_task(&before, retagged).unwrap();
+
+        let mut edited = original.clone();
+        edited.name = "Final report".to_string();
+        edited.priority = Priority::High;
+        tm.update_task(&original, edited).unwrap();
+
+        let task = tm.get_task(id).unwrap();
+        assert_eq!(task.name, "Final report");
+        assert_eq!(task.priority, Priority::High);
+        assert_eq!(task.tags, vec!["theirs".to_string()]);
+        assert_eq!(task.status, TaskStatus::Complete);
+        assert!(task.date_completed.is_some());
+    }
+
+    #[test]
+    fn reopening_a_task_in_an_edit_clears_its_completion_day() {
+        let (_dir, mut tm) = manager();
+        let id = tm.save_task(task("Again")).unwrap();
+        tm.set_task_status(&[id], &TaskStatus::Complete).unwrap();
+
+        let original = tm.get_task(id).unwrap().clone();
+        let mut edited = original.clone();
+        edited.status = TaskStatus::Incomplete;
+        tm.update_task(&original, edited).unwrap();
+
+        let task = tm.get_task(id).unwrap();
+        assert_eq!(task.status, TaskStatus::Incomplete);
+        assert_eq!(task.date_completed, None);
+    }
+
+    #[test]
+    fn dependencies_must_exist_and_not_form_cycles() {
+        let (_dir, mut tm) = manager();
+        let a = tm.save_task(task("A")).unwrap();
+        let mut b = task("B");
+        b.blocked_by = vec![a];
+        let b = tm.save_task(b).unwrap();
+        let mut c = task("C");
+        c.blocked_by = vec![b];
+        let c = tm.save_task(c).unwrap();
+
+        let mut unknown = task("Unknown");
+        unknown.blocked_by = vec![99];
+        assert!(matches!(
+            tm.save_task(unknown),
+            Err(TaskError::NotFound(99))
+        ));
+
+        let original = tm.get_task(a).unwrap().clone();
+        let mut own = original.clone();
+        own.blocked_by = vec![a];
+        assert!(matches!(
+            tm.update_task(&original, own),
+            Err(TaskError::DependencyCycle(_))
+        ));
+
+        let mut indirect = original.clone();
+        indirect.blocked_by = vec![c];
+        match tm.update_task(&original, indirect) {
+            Err(TaskError::DependencyCycle(path)) => {
+                assert_eq!(path.first(), Some(&a));
+                assert_eq!(path.last(), Some(&a));
+                assert!(path.contains(&b) && path.contains(&c));
+            }
+            other => panic!("expected a cycle, got {other:?}"),
+        }
+        assert!(tm.get_task(a).unwrap().blocked_by.is_empty());
+    }
+
+    #[test]
+    fn dependencies_follow_completion_and_deletion() {
+        let (_dir, mut tm) = manager();
+        let a = tm.save_task(task("A")).unwrap();
+        let mut b = task("B");
+        b.blocked_by = vec![a];
+        let b = tm.save_task(b).unwrap();
+
+        assert_eq!(tm.open_blockers(tm.get_task(b).unwrap()), vec![a]);
+        assert_eq!(tm.get_dependents(a), vec![b]);
+
+        tm.set_task_status(&[a], &TaskStatus::Complete).unwrap();
+        assert!(tm.open_blockers(tm.get_task(b).unwrap()).is_empty());
+
+        tm.delete_tasks(&[a]).unwrap();
+        assert!(tm.get_task(b).unwrap().blocked_by.is_empty());
     }
 }
@@ -0,0 +1,96 @@
+use colored::Color;
+
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+/// One band of the urgency scale, e.g. "Yellow: 1 to 6 days left".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UrgencyBand {
+    /// Label used in the status summary.
+    pub name: String,
+    /// Fewest days left that still fall in this band. A band without it
+    /// takes everything below the other bands.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_days: Option<i64>,
+    #[serde(deserialize_with = "deserialize_color")]
+    color: String,
+}
+
+impl UrgencyBand {
+    fn new(name: &str, min_days: Option<i64>, color: &str) -> Self {
+        UrgencyBand {
+            name: name.to_string(),
+            min_days,
+            color: color.to_string(),
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        self.color.as_str().into()
+    }
+}
+
+fn deserialize_color<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let color = String::deserialize(deserializer)?;
+    match color.parse::<Color>() {
+        Ok(_) => Ok(color),
+        Err(()) => Err(de::Error::custom(format!(
+            "unknown color '{color}', expected e.g. red, yellow, bright blue"
+        ))),
+    }
+}
+
+/// How deadlines are grouped by days left, most relaxed band first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "Vec<UrgencyBand>", into = "Vec<UrgencyBand>")]
+pub struct UrgencyScale {
+    bands: Vec<UrgencyBand>,
+}
+
+impl Default for UrgencyScale {
+    fn default() -> Self {
+        UrgencyScale {
+            bands: vec![
+                UrgencyBand::new("Green", Some(7), "green"),
+                UrgencyBand::new("Yellow", Some(1), "yellow"),
+                UrgencyBand::new("Red", Some(0), "red"),
+                UrgencyBand::new("Magenta", None, "magenta"),
+            ],
+        }
+    }
+}
+
+impl UrgencyScale {
+    pub fn bands(&self) -> &[UrgencyBand] {
+        &self.bands
+    }
+
+    /// Index of the band `days` left falls in, `None` if it's below every
+    /// band and there is no catch-all.
+    pub fn classify(&self, days: i64) -> Option<usize> {
+        self.bands
+            .iter()
+            .position(|b| b.min_days.is_none_or(|min| days >= min))
+    }
+}
+
+impl TryFrom<Vec<UrgencyBand>> for UrgencyScale {
+    type Error = String;
+
+    fn try_from(mut bands: Vec<UrgencyBand>) -> Result<Self, Self::Error> {
+        if bands.is_empty() {
+            return Err("the urgency scale needs at least one band".to_string());
+        }
+        // Highest threshold first, the catch-all last.
+        bands.sort_by_key(|b| std::cmp::Reverse(b.min_days.map_or(i128::MIN, i128::from)));
+        Ok(UrgencyScale { bands })
+    }
+}
+
+impl From<UrgencyScale> for Vec<UrgencyBand> {
+    fn from(scale: UrgencyScale) -> Self {
+        scale.bands
+    }
+}